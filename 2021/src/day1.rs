@@ -1,35 +1,37 @@
 use anyhow::Result;
 use itertools::*;
-use serde::Deserialize;
 use std::path::Path;
 
-use crate::read_csv;
-
-#[derive(Debug, Deserialize, PartialEq)]
-struct Day1Input {
-    depth: f64,
-}
+use crate::{input, profile, Solver};
 
 pub fn solve_puzzle(input_path: &Path) -> Result<()> {
-    let input = read_csv(input_path, &["depth"], b'\t')?;
-    println!("Day1, Part1: {}", part1(&input));
-    println!("Day1, Part2: {}", part2(&input));
+    let input = input::lines(&std::fs::read_to_string(input_path)?)?;
+    println!(
+        "Day1, Part1: {}",
+        profile::part(1, 1, || Ok(part1(&input)))?
+    );
+    println!(
+        "Day1, Part2: {}",
+        profile::part(1, 2, || Ok(part2(&input)))?
+    );
     Ok(())
 }
 
-fn part1(input: &[Day1Input]) -> usize {
-    input
-        .iter()
-        .tuple_windows()
-        .filter(|(a, b)| b.depth > a.depth)
-        .count()
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(&input::lines(text)?).to_string()),
+    |text| Ok(part2(&input::lines(text)?).to_string()),
+];
+
+fn part1(input: &[u32]) -> usize {
+    input.iter().tuple_windows().filter(|(a, b)| b > a).count()
 }
 
-fn part2(input: &[Day1Input]) -> usize {
+fn part2(input: &[u32]) -> usize {
     input
         .iter()
         .tuple_windows()
-        .map(|(a, b, c)| a.depth + b.depth + c.depth)
+        .map(|(a, b, c)| a + b + c)
         .tuple_windows()
         .filter(|(a, b)| b > a)
         .count()
@@ -38,19 +40,10 @@ fn part2(input: &[Day1Input]) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{create_input, read_csv};
     use anyhow::Result;
 
-    fn example_puzzle_input() -> Result<Vec<Day1Input>, anyhow::Error> {
-        let dir = tempfile::tempdir()?;
-        let path = create_input(
-            &dir,
-            &[
-                "199", "200", "208", "210", "200", "207", "240", "269", "260", "263",
-            ],
-        )?;
-        let input = read_csv(path.as_path(), &["depth"], b'\t')?;
-        Ok(input)
+    fn example_puzzle_input() -> Result<Vec<u32>> {
+        input::whitespace_separated("199 200 208 210 200 207 240 269 260 263")
     }
 
     #[test]
@@ -1,90 +1,62 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use itertools::Itertools;
 use std::path::Path;
 
+use crate::delimiters::{CompletionScoring, CorruptionScoring, DelimiterChecker, Scoring};
+use crate::{profile, Solver};
+
 pub fn solve_puzzle(input_path: &Path) -> Result<()> {
     let input = std::fs::read_to_string(input_path)?;
-    println!("Day10, Part1: {}", part1(parse_input(&input)));
-    println!("Day10, Part2: {}", part2(parse_input(&input)));
+    println!(
+        "Day10, Part1: {}",
+        profile::part(10, 1, || part1(parse_input(&input)))?
+    );
+    println!(
+        "Day10, Part2: {}",
+        profile::part(10, 2, || part2(parse_input(&input)))?
+    );
     Ok(())
 }
 
-fn parse_input(input: &str) -> Vec<String> {
-    input.lines().map(|l| l.to_owned()).collect_vec()
-}
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(parse_input(text))?.to_string()),
+    |text| Ok(part2(parse_input(text))?.to_string()),
+];
+
+const BRACKETS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
 
-fn parse_line(line: &str) -> Result<Vec<char>, char> {
-    let mut expected_closing = vec![];
+const CORRUPTION_SCORING: CorruptionScoring =
+    CorruptionScoring(&[(')', 3), (']', 57), ('}', 1197), ('>', 25137)]);
 
-    line.chars()
-        .try_for_each(|c| match c {
-            '(' => {
-                expected_closing.push(')');
-                Ok(())
-            }
-            '[' => {
-                expected_closing.push(']');
-                Ok(())
-            }
-            '{' => {
-                expected_closing.push('}');
-                Ok(())
-            }
-            '<' => {
-                expected_closing.push('>');
-                Ok(())
-            }
-            ')' | ']' | '}' | '>' => {
-                if expected_closing
-                    .pop()
-                    .filter(|&expected| expected == c)
-                    .is_some()
-                {
-                    Ok(())
-                } else {
-                    Err(c)
-                }
-            }
-            _ => Err(c),
-        })
-        .and(Ok(expected_closing))
+const COMPLETION_SCORING: CompletionScoring = CompletionScoring {
+    multiplier: 5,
+    points: &[(')', 1), (']', 2), ('}', 3), ('>', 4)],
+};
+
+fn parse_input(input: &str) -> Vec<String> {
+    input.lines().map(|l| l.to_owned()).collect_vec()
 }
 
-fn part1(lines: Vec<String>) -> usize {
-    lines
+fn part1(lines: Vec<String>) -> Result<usize> {
+    let checker = DelimiterChecker::new(&BRACKETS)?;
+    Ok(lines
         .iter()
-        .map(|l| match parse_line(l) {
-            Err(')') => 3,
-            Err(']') => 57,
-            Err('}') => 1197,
-            Err('>') => 25137,
-            _ => 0,
-        })
-        .sum::<usize>()
+        .filter_map(|l| CORRUPTION_SCORING.score(&checker.check(l)))
+        .sum())
 }
 
-fn part2(lines: Vec<String>) -> usize {
-    let mut scores: Vec<usize> = lines
+fn part2(lines: Vec<String>) -> Result<usize> {
+    let checker = DelimiterChecker::new(&BRACKETS)?;
+    let scores = lines
         .iter()
-        .map(|l| parse_line(l))
-        .filter_map(|r| r.ok())
-        .map(|expected| expected.into_iter().rev().collect::<String>())
-        .map(|complection| {
-            complection.chars().fold(0, |acc, c| {
-                acc * 5
-                    + match c {
-                        ')' => 1,
-                        ']' => 2,
-                        '}' => 3,
-                        '>' => 4,
-                        _ => 0,
-                    }
-            })
-        })
+        .filter_map(|l| COMPLETION_SCORING.score(&checker.check(l)))
+        .sorted_unstable()
         .collect_vec();
-
-    scores.sort_unstable();
-    scores[scores.len() / 2]
+    if scores.is_empty() {
+        bail!("no incomplete lines");
+    }
+    Ok(scores[scores.len() / 2])
 }
 
 #[cfg(test)]
@@ -107,11 +79,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(26397, part1(parse_input(INPUT)));
+        assert_eq!(26397, part1(parse_input(INPUT)).unwrap());
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(288957, part2(parse_input(INPUT)));
+        assert_eq!(288957, part2(parse_input(INPUT)).unwrap());
     }
 }
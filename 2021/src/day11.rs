@@ -1,15 +1,31 @@
 use anyhow::Result;
 use image::{GrayImage, Luma};
 use itertools::Itertools;
-use std::path::PathBuf;
+
+use crate::{profile, Solver};
+use crate::visualize::{Grayscale, Grid, Visualizer};
 
 pub fn solve_puzzle() -> Result<()> {
-    const INPUT: &str = include_str!("../assets/day12.txt");
-    println!("Part1: {}", part1(parse_input(INPUT)?)?);
-    println!("Part2: {}", part2(parse_input(INPUT)?)?);
+    const INPUT: &str = include_str!("../assets/day11.txt");
+    println!(
+        "Part1: {}",
+        profile::part(11, 1, || part1(parse_input(INPUT)?))?
+    );
+    println!(
+        "Part2: {}",
+        profile::part(11, 2, || {
+            part2(parse_input(INPUT)?, Visualizer::new("day11_part2")?)
+        })?
+    );
     Ok(())
 }
 
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(parse_input(text)?)?.to_string()),
+    |text| Ok(part2(parse_input(text)?, Visualizer::new("day11_part2")?)?.to_string()),
+];
+
 const ENERGY_INCREASE: u8 = 1;
 const ENERGY_FLASH_THRESHOLD: u8 = 9;
 const ENERGY_FLASH: u8 = 255;
@@ -25,39 +41,6 @@ fn parse_input(input: &str) -> Result<GrayImage> {
         .ok_or(anyhow::anyhow!("Failed to create image"))
 }
 
-struct FrameCount(usize, PathBuf, String);
-impl FrameCount {
-    fn frames(&self) -> usize {
-        self.0
-    }
-
-    fn new(dir: &str, name_format: &str) -> Result<Self> {
-        let pb = PathBuf::from(dir);
-        if !pb.exists() {
-            std::fs::create_dir(&pb)?;
-        }
-        Ok(Self(0, pb, name_format.to_owned()))
-    }
-
-    #[cfg(feature = "visualize")]
-    fn save(&mut self, img: &GrayImage) -> Result<()> {
-        use image::imageops::FilterType;
-
-        let mut pb = self.1.clone();
-        pb.push(format!("{}.{:03}.png", self.2, self.0));
-        let img = image::imageops::resize(img, 256, 256, FilterType::Nearest);
-        img.save(&pb)?;
-        self.0 += 1;
-        Ok(())
-    }
-
-    #[cfg(not(feature = "visualize"))]
-    fn save(&mut self, _: &GrayImage) -> Result<()> {
-        self.0 += 1;
-        Ok(())
-    }
-}
-
 fn part1(mut img: GrayImage) -> Result<usize> {
     let mut flash_count = 0;
 
@@ -73,22 +56,28 @@ fn part1(mut img: GrayImage) -> Result<usize> {
     Ok(flash_count)
 }
 
-fn part2(mut img: GrayImage) -> Result<usize> {
-    let mut frame = FrameCount::new("assets_day11", "part2")?;
-    frame.save(&img)?;
+fn part2(mut img: GrayImage, mut viz: Visualizer) -> Result<usize> {
+    // flashing octopuses are white, the others shaded by their energy
+    let palette = Grayscale::new(ENERGY_FLASH_THRESHOLD + 1);
+    let glyphs = |energy: u8| match energy {
+        ENERGY_FLASH => '*',
+        energy => char::from_digit(energy as u32, 10).unwrap_or('?'),
+    };
+    viz.draw(&Grid::new(&img, &palette).glyphs(&glyphs))?;
 
-    loop {
+    for step in 1.. {
         let mut flashes = increase_energy_and_flash(&mut img);
         while !flashes.is_empty() {
-            flashes.len();
             flashes = flash_adjacent(&mut img, flashes);
         }
-        frame.save(&img)?;
+        viz.draw(&Grid::new(&img, &palette).glyphs(&glyphs))?;
         reset_flashes(&mut img);
         if img.pixels().all(|p| p.0[0] == 0) {
-            return Ok(frame.frames() - 1);
+            viz.finish()?;
+            return Ok(step);
         }
     }
+    unreachable!()
 }
 
 fn increase_energy_and_flash(img: &mut GrayImage) -> Vec<(u32, u32)> {
@@ -142,6 +131,7 @@ fn reset_flashes(img: &mut GrayImage) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::visualize::golden;
     use indoc::indoc;
 
     const INPUT: &str = indoc! {"
@@ -165,7 +155,18 @@ mod tests {
 
     #[test]
     fn test_part2() -> Result<()> {
-        assert_eq!(195, part2(parse_input(INPUT)?)?);
+        assert_eq!(195, part2(parse_input(INPUT)?, golden::disabled())?);
         Ok(())
     }
+
+    #[test]
+    fn golden_part2() -> Result<()> {
+        let (viz, frames) = golden::recorder();
+        part2(parse_input(INPUT)?, viz)?;
+        let frames = frames.borrow();
+        assert_eq!(196, frames.len());
+        golden::assert_golden("day11_part2_start", &frames[0])?;
+        golden::assert_golden("day11_part2_step2", &frames[2])?;
+        golden::assert_golden("day11_part2_end", &frames[195])
+    }
 }
@@ -1,16 +1,33 @@
 use anyhow::Result;
+use image::Rgb;
 use itertools::Itertools;
 use petgraph::{graph::NodeIndex, Graph, Undirected};
 use std::collections::HashMap;
 use smallvec::{smallvec, SmallVec};
 
+use crate::{profile, Solver};
+use crate::visualize::export::{self, graph_dot, graph_svg, NodeStyle};
+
 pub fn solve_puzzle() -> Result<()> {
     const INPUT: &str = include_str!("../assets/day12.txt");
-    println!("Part1: {}", part1(parse_input(INPUT)));
-    println!("Part2: {}", part2(parse_input(INPUT)));
+    export(&parse_input(INPUT).1)?;
+    println!(
+        "Part1: {}",
+        profile::part(12, 1, || Ok(part1(parse_input(INPUT))))?
+    );
+    println!(
+        "Part2: {}",
+        profile::part(12, 2, || Ok(part2(parse_input(INPUT))))?
+    );
     Ok(())
 }
 
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(parse_input(text)).to_string()),
+    |text| Ok(part2(parse_input(text)).to_string()),
+];
+
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 enum CaveSize {
     Small,
@@ -28,17 +45,16 @@ impl From<&str> for CaveSize {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-struct CaveData {
-    #[allow(dead_code)]
-    name: &'static str,
+struct CaveData<'a> {
+    name: &'a str,
     size: CaveSize,
 }
 
 fn parse_input(
-    input: &'static str,
+    input: &str,
 ) -> (
-    HashMap<&'static str, NodeIndex>,
-    Graph<CaveData, usize, Undirected>,
+    HashMap<&str, NodeIndex>,
+    Graph<CaveData<'_>, usize, Undirected>,
 ) {
     let mut node_indices = HashMap::new();
     let mut graph = Graph::<CaveData, usize, Undirected>::new_undirected();
@@ -63,13 +79,24 @@ fn parse_input(
     (node_indices, graph)
 }
 
+/// Writes the caves as Graphviz DOT and SVG, with big caves in red and small ones in blue.
+fn export(graph: &Graph<CaveData, usize, Undirected>) -> Result<()> {
+    let style = |cave: &CaveData| NodeStyle {
+        label: cave.name.to_owned(),
+        color: match (cave.name, cave.size) {
+            ("start" | "end", _) => Rgb([144, 238, 144]),
+            (_, CaveSize::Big) => Rgb([240, 128, 128]),
+            (_, CaveSize::Small) => Rgb([135, 206, 250]),
+        },
+    };
+    export::save("day12_caves.dot", || Ok(graph_dot(graph, style)))?;
+    export::save("day12_caves.svg", || Ok(graph_svg(graph, style)))
+}
+
 const SMALLVEC_SIZE: usize = 24;
 
 fn part1(
-    (node_indices, graph): (
-        HashMap<&'static str, NodeIndex>,
-        Graph<CaveData, usize, Undirected>,
-    ),
+    (node_indices, graph): (HashMap<&str, NodeIndex>, Graph<CaveData, usize, Undirected>),
 ) -> usize {
     let start = *node_indices.get("start").unwrap();
     let end = *node_indices.get("end").unwrap();
@@ -112,10 +139,7 @@ fn part1(
 }
 
 fn part2(
-    (node_indices, graph): (
-        HashMap<&'static str, NodeIndex>,
-        Graph<CaveData, usize, Undirected>,
-    ),
+    (node_indices, graph): (HashMap<&str, NodeIndex>, Graph<CaveData, usize, Undirected>),
 ) -> usize {
     let start = *node_indices.get("start").unwrap();
     let end = *node_indices.get("end").unwrap();
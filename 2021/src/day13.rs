@@ -1,22 +1,37 @@
 use anyhow::Result;
 use image::{GrayImage, Luma};
 use itertools::Itertools;
-use std::path::PathBuf;
 
 use self::parser::{parse, Fold, ParsedInput};
+use crate::{profile, Solver};
+use crate::visualize::{Grayscale, Grid, Visualizer};
 
+mod ocr;
 mod parser;
 
 pub fn solve_puzzle() -> Result<()> {
     const INPUT: &str = include_str!("../assets/day13.txt");
-    println!("Part1: {}", part1(parse_input(INPUT)?)?);
-    part2(parse_input(INPUT)?)?;
-    println!("Part2: it's an image!",);
+    println!(
+        "Part1: {}",
+        profile::part(13, 1, || part1(parse_input(INPUT)?))?
+    );
+    println!(
+        "Part2: {}",
+        profile::part(13, 2, || {
+            part2(parse_input(INPUT)?, Visualizer::new("day13_part2")?)
+        })?
+    );
     Ok(())
 }
 
-fn parse_input(input: &'static str) -> Result<ParsedInput> {
-    let (_, parsed) = parse(input)?;
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(parse_input(text)?)?.to_string()),
+    |text| part2(parse_input(text)?, Visualizer::new("day13_part2")?),
+];
+
+fn parse_input(input: &str) -> Result<ParsedInput> {
+    let (_, parsed) = parse(input).map_err(|e| e.to_owned())?;
     Ok(parsed)
 }
 
@@ -27,33 +42,31 @@ fn part1(mut input: ParsedInput) -> Result<usize> {
     Ok(input.dots.into_iter().unique().count())
 }
 
-fn part2(mut input: ParsedInput) -> Result<()> {
+fn part2(mut input: ParsedInput, viz: Visualizer) -> Result<String> {
     follow_instructions(input.instructions.into_iter(), &mut input.dots);
 
-    // finalize as an image
-    let (max_x, max_y) = input
-        .dots
+    visualize(&input.dots, viz)?;
+
+    ocr::read_letters(&input.dots)
+}
+
+fn visualize(dots: &[(u32, u32)], mut viz: Visualizer) -> Result<()> {
+    if !viz.is_enabled() {
+        return Ok(());
+    }
+    let (max_x, max_y) = dots
         .iter()
         .fold((0, 0), |(x1, y1), (x2, y2)| (x1.max(*x2), y1.max(*y2)));
     let mut img = GrayImage::new(max_x + 1, max_y + 1);
-    for (x, y) in input.dots.into_iter() {
-        img.put_pixel(x, y, Luma([255]));
+    for &(x, y) in dots {
+        img.put_pixel(x, y, Luma([1]));
     }
-
-    // save image
-    let mut pb = PathBuf::from("assets_day13");
-    if !pb.exists() {
-        std::fs::create_dir(&pb)?;
-    }
-    pb.push("part2.png");
-
-    #[cfg(feature = "visualize")]
-    img.save(pb.as_path())?;
-
-    Ok(())
+    let glyphs = |v: u8| if v > 0 { '#' } else { '.' };
+    viz.draw(&Grid::new(&img, &Grayscale::new(1)).glyphs(&glyphs))?;
+    viz.finish()
 }
 
-fn follow_instructions(instructions: impl Iterator<Item = Fold>, dots: &mut Vec<(u32, u32)>) {
+fn follow_instructions(instructions: impl Iterator<Item = Fold>, dots: &mut [(u32, u32)]) {
     for instr in instructions {
         match instr {
             Fold::AlongX(n) => dots.iter_mut().for_each(|(x, _)| {
@@ -73,6 +86,7 @@ fn follow_instructions(instructions: impl Iterator<Item = Fold>, dots: &mut Vec<
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::visualize::golden;
     use indoc::indoc;
 
     const INPUT: &str = indoc! {"
@@ -107,6 +121,23 @@ mod tests {
 
     #[test]
     fn test_part2() -> Result<()> {
-        part2(parse_input(INPUT)?)
+        // the example folds into a square, which isn't a letter
+        assert!(part2(parse_input(INPUT)?, golden::disabled()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn golden_part2() -> Result<()> {
+        let (viz, frames) = golden::recorder();
+        let _ = part2(parse_input(INPUT)?, viz);
+        let frames = frames.borrow();
+        golden::assert_golden("day13_part2", &frames[0])
+    }
+
+    #[test]
+    fn test_part2_input() -> Result<()> {
+        const INPUT: &str = include_str!("../assets/day13.txt");
+        assert_eq!("FGKCKBZG", part2(parse_input(INPUT)?, golden::disabled())?);
+        Ok(())
     }
 }
@@ -0,0 +1,153 @@
+//! Recognition of the 4x6 letter font used by Advent of Code puzzles.
+//!
+//! Letters are 4 dots wide and 6 dots high, and are laid out left to right with a single empty
+//! column between them.
+
+use anyhow::{bail, Result};
+
+const GLYPH_WIDTH: u32 = 4;
+const GLYPH_HEIGHT: u32 = 6;
+const GLYPH_SPACING: u32 = 1;
+
+const GLYPHS: [(char, [&str; GLYPH_HEIGHT as usize]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Bit pattern of a single glyph; bit `y * GLYPH_WIDTH + x` is set when the dot at `(x, y)` is.
+type GlyphBits = u32;
+
+fn glyph_bits(rows: &[&str]) -> GlyphBits {
+    rows.iter()
+        .flat_map(|row| row.chars())
+        .enumerate()
+        .filter(|(_, c)| *c == '#')
+        .fold(0, |bits, (i, _)| bits | 1 << i)
+}
+
+/// Reads the letters spelled out by `dots`.
+///
+/// The dots are moved up so the top most dot ends up in the top row, and left by whole letters, as
+/// the first column of a letter like `I` is empty. Fails if the dots don't fit inside a single line
+/// of text, or if any letter is unknown.
+pub(crate) fn read_letters(dots: &[(u32, u32)]) -> Result<String> {
+    let (min_x, min_y, max_x, max_y) = match bounds(dots) {
+        Some(bounds) => bounds,
+        None => bail!("no dots to read letters from"),
+    };
+    if max_y - min_y >= GLYPH_HEIGHT {
+        bail!(
+            "dots span {} rows, letters are {} rows high",
+            max_y - min_y + 1,
+            GLYPH_HEIGHT
+        );
+    }
+
+    let pitch = GLYPH_WIDTH + GLYPH_SPACING;
+    let min_x = min_x - min_x % pitch;
+    let letter_count = (max_x - min_x) / pitch + 1;
+    let mut letters = vec![0 as GlyphBits; letter_count as usize];
+    for (x, y) in dots.iter().map(|(x, y)| (x - min_x, y - min_y)) {
+        let (letter, x) = (x / pitch, x % pitch);
+        if x >= GLYPH_WIDTH {
+            bail!("dot in the spacing after letter {}", letter + 1);
+        }
+        letters[letter as usize] |= 1 << (y * GLYPH_WIDTH + x);
+    }
+
+    letters
+        .into_iter()
+        .enumerate()
+        .map(|(n, bits)| {
+            GLYPHS
+                .iter()
+                .find(|(_, rows)| glyph_bits(rows) == bits)
+                .map(|(c, _)| *c)
+                .ok_or_else(|| anyhow::anyhow!("unknown glyph at letter {}", n + 1))
+        })
+        .collect()
+}
+
+/// Returns `(min_x, min_y, max_x, max_y)` of the dots, or `None` if there are no dots.
+fn bounds(dots: &[(u32, u32)]) -> Option<(u32, u32, u32, u32)> {
+    let (&(x, y), rest) = dots.split_first()?;
+    Some(rest.iter().fold((x, y, x, y), |(x1, y1, x2, y2), &(x, y)| {
+        (x1.min(x), y1.min(y), x2.max(x), y2.max(y))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    fn render(text: &str) -> Vec<(u32, u32)> {
+        text.chars()
+            .enumerate()
+            .flat_map(|(n, c)| {
+                let (_, rows) = GLYPHS.iter().find(|(g, _)| *g == c).unwrap();
+                rows.iter().enumerate().flat_map(move |(y, row)| {
+                    row.chars()
+                        .enumerate()
+                        .filter(|(_, c)| *c == '#')
+                        .map(move |(x, _)| {
+                            (
+                                n as u32 * (GLYPH_WIDTH + GLYPH_SPACING) + x as u32,
+                                y as u32,
+                            )
+                        })
+                })
+            })
+            .collect_vec()
+    }
+
+    #[test]
+    fn reads_every_glyph() -> Result<()> {
+        let alphabet = GLYPHS.iter().map(|(c, _)| *c).collect::<String>();
+        assert_eq!(alphabet, read_letters(&render(&alphabet))?);
+        Ok(())
+    }
+
+    #[test]
+    fn ignores_offset_and_duplicates() -> Result<()> {
+        let mut dots = render("HELLO")
+            .into_iter()
+            .map(|(x, y)| (x + 2 * (GLYPH_WIDTH + GLYPH_SPACING), y + 7))
+            .collect_vec();
+        dots.extend(dots.clone());
+        assert_eq!("HELLO", read_letters(&dots)?);
+        Ok(())
+    }
+
+    #[test]
+    fn reads_codes_starting_with_an_empty_column() -> Result<()> {
+        assert_eq!("IJK", read_letters(&render("IJK"))?);
+        assert_eq!("JAIL", read_letters(&render("JAIL"))?);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs() {
+        let square = (0..5)
+            .flat_map(|i| [(i, 0), (i, 4), (0, i), (4, i)])
+            .collect_vec();
+        assert!(read_letters(&square).is_err());
+        assert!(read_letters(&[]).is_err());
+        assert!(read_letters(&[(0, 0), (0, 6)]).is_err());
+    }
+}
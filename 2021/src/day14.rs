@@ -3,18 +3,31 @@ use itertools::{Itertools, MinMaxResult};
 use std::collections::HashMap;
 
 use self::parser::{parse, ParsedInput};
+use crate::{profile, Solver};
 
 mod parser;
 
 pub fn solve_puzzle() -> Result<()> {
     const INPUT: &str = include_str!("../assets/day14.txt");
-    println!("Part1: {}", part1(parse_input(INPUT)?)?);
-    println!("Part2: {}", part2(parse_input(INPUT)?)?);
+    println!(
+        "Part1: {}",
+        profile::part(14, 1, || part1(parse_input(INPUT)?))?
+    );
+    println!(
+        "Part2: {}",
+        profile::part(14, 2, || part2(parse_input(INPUT)?))?
+    );
     Ok(())
 }
 
-fn parse_input(input: &'static str) -> Result<ParsedInput> {
-    let (_, parsed) = parse(input)?;
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(parse_input(text)?)?.to_string()),
+    |text| Ok(part2(parse_input(text)?)?.to_string()),
+];
+
+fn parse_input(input: &str) -> Result<ParsedInput> {
+    let (_, parsed) = parse(input).map_err(|e| e.to_owned())?;
     Ok(parsed)
 }
 
@@ -82,7 +95,7 @@ fn part2(input: ParsedInput) -> Result<usize> {
         *counts.entry(k2).or_default() += v;
     }
 
-    match counts.into_iter().map(|(_, v)| v / 2).minmax() {
+    match counts.into_values().map(|v| v / 2).minmax() {
         MinMaxResult::NoElements => bail!("no min & max"),
         MinMaxResult::OneElement(_) => bail!("only 1 element"),
         MinMaxResult::MinMax(min, max) => Ok(max - min),
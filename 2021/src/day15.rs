@@ -1,25 +1,47 @@
 use anyhow::{bail, Result};
+use image::{GrayImage, Luma, Rgb};
 use ndarray::{Array2, Axis};
 use petgraph::algo::astar;
 use petgraph::graphmap::UnGraphMap;
 
 use self::parser::parse_input;
+use crate::{profile, Solver};
+use crate::visualize::{
+    export::{self, path_svg},
+    Grid, Ramp,
+};
 
 mod parser;
 
 pub fn solve_puzzle() -> Result<()> {
     const INPUT: &str = include_str!("../assets/day15.txt");
-    println!("Part1: {}", part1(parse_input(INPUT)?)?);
-    println!("Part2: {}", part2(parse_input(INPUT)?)?);
+    println!(
+        "Part1: {}",
+        profile::part(15, 1, || part1(parse_input(INPUT)?))?
+    );
+    println!(
+        "Part2: {}",
+        profile::part(15, 2, || part2(parse_input(INPUT)?))?
+    );
     Ok(())
 }
 
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(parse_input(text)?)?.to_string()),
+    |text| Ok(part2(parse_input(text)?)?.to_string()),
+];
+
 fn part1(node_weights: Array2<u16>) -> Result<usize> {
+    lowest_risk(node_weights, "day15_part1")
+}
+
+fn lowest_risk(node_weights: Array2<u16>, name: &str) -> Result<usize> {
     // create a undirected graph with u32 for node index, and () for edge weights
     let mut graph = UnGraphMap::<u32, ()>::new();
 
     // create connections in the graph
-    let row_size = node_weights.len_of(Axis(1)) as usize;
+    let row_size = node_weights.len_of(Axis(1));
     let windows_per_row = row_size - 1;
     node_weights
         .windows((2, 2))
@@ -46,11 +68,33 @@ fn part1(node_weights: Array2<u16>) -> Result<usize> {
         |e| node_weights.as_slice().unwrap()[e.1 as usize],
         |_n| 0,
     ) {
-        Some((cost, _nodes)) => Ok(cost as usize),
+        Some((cost, nodes)) => {
+            export(&node_weights, &nodes, name)?;
+            Ok(cost as usize)
+        }
         None => bail!("no path through the graph!"),
     }
 }
 
+/// Writes the risk levels as SVG, with the path of the lowest risk on top.
+fn export(node_weights: &Array2<u16>, nodes: &[u32], name: &str) -> Result<()> {
+    export::save(&format!("{}_path.svg", name), || {
+        let (height, width) = node_weights.dim();
+        let risks = GrayImage::from_fn(width as u32, height as u32, |x, y| {
+            Luma([node_weights[[y as usize, x as usize]] as u8])
+        });
+        let path = nodes
+            .iter()
+            .map(|&n| (n % width as u32, n / width as u32))
+            .collect::<Vec<_>>();
+        path_svg(
+            &Grid::new(&risks, &Ramp::viridis(9)),
+            &path,
+            Rgb([255, 0, 0]),
+        )
+    })
+}
+
 fn part2(input: Array2<u16>) -> Result<usize> {
     let (dim_y, dim_x) = input.dim();
     let expanded = Array2::from_shape_fn((dim_y * 5, dim_x * 5), |(y, x)| {
@@ -64,7 +108,7 @@ fn part2(input: Array2<u16>) -> Result<usize> {
             r
         }
     });
-    part1(expanded)
+    lowest_risk(expanded, "day15_part2")
 }
 
 #[cfg(test)]
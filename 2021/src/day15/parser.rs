@@ -9,8 +9,8 @@ use nom::{
 };
 use std::str::FromStr;
 
-pub(crate) fn parse_input(input: &'static str) -> Result<Array2<u16>> {
-    let (_, (cols, node_weights)) = parse(input)?;
+pub(crate) fn parse_input(input: &str) -> Result<Array2<u16>> {
+    let (_, (cols, node_weights)) = parse(input).map_err(|e| e.to_owned())?;
     let node_weights = Array::from_shape_vec(
         (node_weights.len() / cols as usize, cols as usize),
         node_weights,
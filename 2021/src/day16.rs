@@ -1,15 +1,28 @@
 use self::parser::*;
+use crate::{profile, Solver};
 use anyhow::Result;
 
 mod parser;
 
 pub fn solve_puzzle() -> Result<()> {
     const INPUT: &str = include_str!("../assets/day16.txt");
-    println!("Part1: {}", part1(parse_input(INPUT)?)?);
-    println!("Part2: {}", part2(parse_input(INPUT)?)?);
+    println!(
+        "Part1: {}",
+        profile::part(16, 1, || part1(parse_input(INPUT)?))?
+    );
+    println!(
+        "Part2: {}",
+        profile::part(16, 2, || part2(parse_input(INPUT)?))?
+    );
     Ok(())
 }
 
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(parse_input(text)?)?.to_string()),
+    |text| Ok(part2(parse_input(text)?)?.to_string()),
+];
+
 fn part1(input: Packet) -> Result<usize> {
     fn version_sum(packet: &Packet) -> usize {
         packet.version.0 as usize
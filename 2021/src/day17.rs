@@ -1,11 +1,16 @@
 use anyhow::Result;
 
+use crate::{profile, Solver};
+
 pub fn solve_puzzle() -> Result<()> {
-    println!("Part1: {}", part1()?);
-    println!("Part2: {}", part2()?);
+    println!("Part1: {}", profile::part(17, 1, part1)?);
+    println!("Part2: {}", profile::part(17, 2, part2)?);
     Ok(())
 }
 
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [|_| Ok(part1()?.to_string()), |_| Ok(part2()?.to_string())];
+
 fn part1() -> Result<i32> {
     // Due to preservation of energy, whatever speed by which we initially throw upwards, our
     // probe will have that same speed when it comes back to y-position 0.
@@ -1,91 +1,158 @@
-use anyhow::Result;
-use serde::Deserialize;
-use std::path::Path;
+use anyhow::{anyhow, bail, Context, Result};
+use std::{path::Path, str::FromStr};
 
-use crate::read_csv;
+use crate::{input, profile, Solver};
 
-#[derive(Debug, Deserialize, PartialEq)]
-struct Day2Input {
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Command {
     direction: Direction,
     steps: i32,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, PartialEq, Clone, Copy)]
 enum Direction {
     Up,
     Down,
     Forward,
 }
 
-#[derive(Debug, PartialEq, Default)]
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
 struct SubmarinePosition {
     horizontal: i32,
     vertical: i32,
     aim: i32,
 }
 
+impl FromStr for Direction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "up" => Ok(Direction::Up),
+            "down" => Ok(Direction::Down),
+            "forward" => Ok(Direction::Forward),
+            _ => bail!("unknown command '{}'", s),
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut words = s.split_whitespace();
+        let direction = words.next().ok_or_else(|| anyhow!("missing command"))?;
+        let direction = direction.parse()?;
+        let steps = words.next().ok_or_else(|| anyhow!("missing steps"))?;
+        let steps = steps
+            .parse()
+            .with_context(|| format!("invalid steps '{}'", steps))?;
+        if let Some(extra) = words.next() {
+            bail!("unexpected '{}' after the steps", extra);
+        }
+        Ok(Command { direction, steps })
+    }
+}
+
+/// How a command moves the submarine.
+trait Semantics {
+    fn apply(&self, position: &mut SubmarinePosition, command: &Command);
+}
+
+/// Up and down change the depth directly.
+struct Plain;
+
+impl Semantics for Plain {
+    fn apply(&self, position: &mut SubmarinePosition, &Command { direction, steps }: &Command) {
+        match direction {
+            Direction::Up => position.vertical -= steps,
+            Direction::Down => position.vertical += steps,
+            Direction::Forward => position.horizontal += steps,
+        }
+    }
+}
+
+/// Up and down change the aim, which changes the depth when moving forward.
+struct Aim;
+
+impl Semantics for Aim {
+    fn apply(&self, position: &mut SubmarinePosition, &Command { direction, steps }: &Command) {
+        match direction {
+            Direction::Up => position.aim -= steps,
+            Direction::Down => position.aim += steps,
+            Direction::Forward => {
+                position.horizontal += steps;
+                position.vertical += position.aim * steps;
+            }
+        }
+    }
+}
+
+/// The position after each of the commands.
+fn trace<'a>(
+    commands: &'a [Command],
+    semantics: &'a impl Semantics,
+) -> impl Iterator<Item = SubmarinePosition> + 'a {
+    commands
+        .iter()
+        .scan(SubmarinePosition::default(), move |position, command| {
+            semantics.apply(position, command);
+            Some(*position)
+        })
+}
+
+fn run(commands: &[Command], semantics: &impl Semantics) -> SubmarinePosition {
+    trace(commands, semantics).last().unwrap_or_default()
+}
+
 pub fn solve_puzzle(input_path: &Path) -> Result<()> {
-    let input = read_csv(input_path, &["direction", "steps"], b' ')?;
-    println!("Day2, Part1: {}", part1(&input));
-    println!("Day2, Part2: {}", part2(&input));
+    let input = parse_input(&std::fs::read_to_string(input_path)?)?;
+    println!(
+        "Day2, Part1: {}",
+        profile::part(2, 1, || Ok(part1(&input)))?
+    );
+    println!(
+        "Day2, Part2: {}",
+        profile::part(2, 2, || Ok(part2(&input)))?
+    );
     Ok(())
 }
 
-fn part1(input: &[Day2Input]) -> i32 {
-    let r = input.iter().fold(
-        SubmarinePosition::default(),
-        |mut acc, Day2Input { direction, steps }| {
-            match direction {
-                Direction::Up => acc.vertical -= steps,
-                Direction::Down => acc.vertical += steps,
-                Direction::Forward => acc.horizontal += steps,
-            };
-            acc
-        },
-    );
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(&parse_input(text)?).to_string()),
+    |text| Ok(part2(&parse_input(text)?).to_string()),
+];
+
+fn parse_input(input: &str) -> Result<Vec<Command>> {
+    input::lines(input)
+}
+
+fn part1(input: &[Command]) -> i32 {
+    let r = run(input, &Plain);
     r.horizontal * r.vertical
 }
 
-fn part2(input: &[Day2Input]) -> i32 {
-    let r = input.iter().fold(
-        SubmarinePosition::default(),
-        |mut acc, Day2Input { direction, steps }| {
-            match direction {
-                Direction::Up => acc.aim -= steps,
-                Direction::Down => acc.aim += steps,
-                Direction::Forward => {
-                    acc.horizontal += steps;
-                    acc.vertical += acc.aim * steps;
-                }
-            };
-            acc
-        },
-    );
+fn part2(input: &[Command]) -> i32 {
+    let r = run(input, &Aim);
     r.horizontal * r.vertical
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{create_input, read_csv};
     use anyhow::Result;
+    use indoc::indoc;
 
-    fn example_input() -> Result<Vec<Day2Input>, anyhow::Error> {
-        let dir = tempfile::tempdir()?;
-        let input_path = create_input(
-            &dir,
-            &[
-                "forward 5",
-                "down 5",
-                "forward 8",
-                "up 3",
-                "down 8",
-                "forward 2",
-            ],
-        )?;
-        let input = read_csv(input_path.as_path(), &["direction", "steps"], b' ')?;
-        Ok(input)
+    fn example_input() -> Result<Vec<Command>> {
+        parse_input(indoc! {"
+            forward 5
+            down 5
+            forward 8
+            up 3
+            down 8
+            forward 2
+        "})
     }
 
     #[test]
@@ -101,4 +168,35 @@ mod tests {
         assert_eq!(900, part2(&input));
         Ok(())
     }
+
+    #[test]
+    fn test_trace() -> Result<()> {
+        let input = example_input()?;
+        let positions = trace(&input, &Aim)
+            .map(|p| (p.horizontal, p.vertical, p.aim))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (5, 0, 0),
+                (5, 0, 5),
+                (13, 40, 5),
+                (13, 40, 2),
+                (13, 40, 10),
+                (15, 60, 10)
+            ],
+            positions
+        );
+        assert_eq!(None, trace(&[], &Plain).next());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_command() {
+        let err = parse_input("forward 5\n\nsideways 3\n").unwrap_err();
+        assert_eq!("line 3", err.to_string());
+        assert_eq!("unknown command 'sideways'", err.root_cause().to_string());
+        assert!(parse_input("forward").is_err());
+        assert!(parse_input("forward x").is_err());
+        assert!(parse_input("forward 1 2").is_err());
+    }
 }
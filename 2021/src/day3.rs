@@ -1,157 +1,175 @@
-use anyhow::Result;
-use serde::Deserialize;
-use std::{collections::HashMap, path::Path};
+use anyhow::{bail, Result};
+use std::{cmp::Ordering, path::Path};
 
-use crate::read_csv;
-
-#[derive(Debug, Deserialize, PartialEq)]
-struct Day3Input {
-    bits: String,
-}
+use crate::{input, profile, Solver};
 
 pub fn solve_puzzle(input_path: &Path) -> Result<()> {
-    let input = read_csv(input_path, &["bits"], b' ')?;
-    println!("Day3, Part1: {}", part1(&input));
-    println!("Day3, Part2: {}", part2(&input));
+    let input = parse_input(&std::fs::read_to_string(input_path)?)?;
+    println!(
+        "Day3, Part1: {}",
+        profile::part(3, 1, || Ok(part1(&input)))?
+    );
+    println!("Day3, Part2: {}", profile::part(3, 2, || part2(&input))?);
     Ok(())
 }
 
-fn part1(input: &[Day3Input]) -> u32 {
-    let input = input
-        .iter()
-        .map(|i| i.bits.clone())
-        .collect::<Vec<String>>();
-    let bits_in_input = input[0].chars().count();
-
-    let (lines_in_input, bit_counts) = count_ones_by_bitnumber(&input);
-    let mut gamma = 0u32;
-    let mut epsilon = 0u32;
-    for bit in 0..bits_in_input {
-        let count = bit_counts[&bit];
-        let (mc, lc) = if lines_in_input - count < count {
-            (1, 0)
-        } else {
-            (0, 1)
-        };
-        gamma |= mc << bit;
-        epsilon |= lc << bit;
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(&parse_input(text)?).to_string()),
+    |text| Ok(part2(&parse_input(text)?)?.to_string()),
+];
+
+/// Diagnostic report, with every line stored as an integer of `width` bits.
+#[derive(Debug, PartialEq)]
+struct Report {
+    width: u32,
+    values: Vec<u32>,
+}
+
+fn parse_input(input: &str) -> Result<Report> {
+    let mut width = None;
+    let mut values = vec![];
+    for (n, line) in input::numbered_lines(input) {
+        if line.len() > u32::BITS as usize {
+            bail!("line {}: more than {} bits", n, u32::BITS);
+        }
+        match width {
+            None => width = Some(line.len() as u32),
+            Some(w) if w != line.len() as u32 => {
+                bail!("line {}: {} bits, expected {}", n, line.len(), w)
+            }
+            _ => {}
+        }
+        let value = line.chars().try_fold(0, |value, c| match c {
+            '0' => Ok(value << 1),
+            '1' => Ok(value << 1 | 1),
+            _ => bail!("line {}: invalid bit '{}'", n, c),
+        })?;
+        values.push(value);
     }
-    gamma * epsilon
+    Ok(Report {
+        width: width.unwrap_or_default(),
+        values,
+    })
 }
 
-fn part2(input: &[Day3Input]) -> u32 {
-    let input = input
-        .iter()
-        .map(|i| i.bits.clone())
-        .collect::<Vec<String>>();
-    let oxygen = get_oxygen(&input);
-    let co2 = get_co2_scrubber(&input);
-    oxygen * co2
+/// Which value of a bit to keep when filtering by it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Keep {
+    MostCommon,
+    LeastCommon,
 }
 
-fn count_ones_by_bitnumber(input: &[String]) -> (u32, HashMap<usize, u32>) {
-    let mut bit_map: HashMap<usize, u32> = HashMap::new();
-    let mut lines = 0;
-    for line in input.iter() {
-        lines += 1;
-        let len = line.chars().count();
-        for (bit, value) in line.chars().enumerate() {
-            let bit = len - bit - 1;
-            let value = match value {
-                '0' => 0,
-                _ => 1,
-            };
-            if let Some(entry) = bit_map.get_mut(&bit) {
-                *entry += value;
-            } else {
-                bit_map.insert(bit, value);
-            }
+/// Selects a bit value from how often each value occurs, using `on_tie` when they are equally
+/// common.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BitCriteria {
+    keep: Keep,
+    on_tie: u32,
+}
+
+impl BitCriteria {
+    const OXYGEN: Self = Self {
+        keep: Keep::MostCommon,
+        on_tie: 1,
+    };
+    const CO2_SCRUBBER: Self = Self {
+        keep: Keep::LeastCommon,
+        on_tie: 0,
+    };
+
+    fn select(&self, zeros: usize, ones: usize) -> u32 {
+        match (zeros.cmp(&ones), self.keep) {
+            (Ordering::Equal, _) => self.on_tie,
+            (Ordering::Less, Keep::MostCommon) | (Ordering::Greater, Keep::LeastCommon) => 1,
+            (Ordering::Greater, Keep::MostCommon) | (Ordering::Less, Keep::LeastCommon) => 0,
         }
     }
+}
 
-    (lines, bit_map)
+fn part1(input: &Report) -> u32 {
+    let ones = column_ones(input);
+    let rate = |criteria: BitCriteria| {
+        ones.iter().enumerate().fold(0, |rate, (bit, &ones)| {
+            let zeros = input.values.len() - ones;
+            rate | criteria.select(zeros, ones) << bit
+        })
+    };
+    let gamma = rate(BitCriteria::OXYGEN);
+    let epsilon = rate(BitCriteria::CO2_SCRUBBER);
+    gamma * epsilon
 }
 
-fn fold_by_bits_from_right(
-    mut input: Vec<String>,
-    func: impl Fn(usize, usize, &[String]) -> char,
-) -> Option<String> {
-    let bits_in_input = input.get(0).map(|l| l.chars().count()).unwrap_or_default();
-
-    for bit in 0..bits_in_input {
-        let lookfor = func(bits_in_input, bit, &input);
-        input = input
-            .into_iter()
-            .filter(|line| {
-                line.chars()
-                    .skip(bit)
-                    .map(|c| lookfor == c)
-                    .next()
-                    .unwrap_or_default()
-            })
-            .collect();
-        if input.len() <= 1 {
-            return Some(input[0].clone());
-        }
+fn part2(input: &Report) -> Result<u32> {
+    let mut sorted = input.values.clone();
+    sorted.sort_unstable();
+    match (
+        rating(&sorted, input.width, BitCriteria::OXYGEN),
+        rating(&sorted, input.width, BitCriteria::CO2_SCRUBBER),
+    ) {
+        (Some(oxygen), Some(co2)) => Ok(oxygen * co2),
+        _ => bail!("no ratings in an empty report"),
     }
-    None
 }
 
-fn get_oxygen(input: &[String]) -> u32 {
-    fn by_most_significant(bits_in_input: usize, bit: usize, input: &[String]) -> char {
-        let (lines_in_input, bit_counts) = count_ones_by_bitnumber(input);
-        let count = bit_counts
-            .get(&(bits_in_input - bit - 1))
-            .copied()
-            .unwrap_or_default();
-        if count >= lines_in_input - count {
-            '1'
-        } else {
-            '0'
+/// Number of ones in each bit column, indexed by bit number.
+fn column_ones(input: &Report) -> Vec<usize> {
+    let mut ones = vec![0; input.width as usize];
+    for &value in &input.values {
+        // visit the set bits only, clearing the lowest one at a time
+        let mut v = value;
+        while v != 0 {
+            ones[v.trailing_zeros() as usize] += 1;
+            v &= v - 1;
         }
     }
-
-    fold_by_bits_from_right(input.to_vec(), by_most_significant)
-        .map(|s| u32::from_str_radix(&s, 2).unwrap_or_default())
-        .unwrap_or_default()
+    ones
 }
 
-fn get_co2_scrubber(input: &[String]) -> u32 {
-    fn by_least_significant(bits_in_input: usize, bit: usize, input: &[String]) -> char {
-        let (lines_in_input, bit_counts) = count_ones_by_bitnumber(input);
-        let count = bit_counts
-            .get(&(bits_in_input - bit - 1))
-            .copied()
-            .unwrap_or_default();
-        if count >= lines_in_input - count {
-            '0'
-        } else {
-            '1'
+/// Filters the values bit by bit from the most significant one until a single value remains.
+///
+/// As `sorted` is sorted, the remaining values always form a range, within which the values with
+/// the current bit cleared come before the ones with it set. Each bit therefore only needs a
+/// binary search to split the range. A bit value that doesn't occur is never selected.
+fn rating(sorted: &[u32], width: u32, criteria: BitCriteria) -> Option<u32> {
+    let mut range = sorted;
+    for bit in (0..width).rev() {
+        if range.len() <= 1 {
+            break;
         }
+        let split = range.partition_point(|v| v & 1 << bit == 0);
+        let (zeros, ones) = range.split_at(split);
+        range = match criteria.select(zeros.len(), ones.len()) {
+            _ if zeros.is_empty() => ones,
+            _ if ones.is_empty() => zeros,
+            0 => zeros,
+            _ => ones,
+        };
     }
-
-    fold_by_bits_from_right(input.to_vec(), by_least_significant)
-        .map(|s| u32::from_str_radix(&s, 2).unwrap_or_default())
-        .unwrap_or_default()
+    range.first().copied()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{create_input, read_csv};
     use anyhow::Result;
-
-    fn example_input() -> Result<Vec<Day3Input>, anyhow::Error> {
-        let dir = tempfile::tempdir()?;
-        let input_path = create_input(
-            &dir,
-            &[
-                "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000",
-                "11001", "00010", "01010",
-            ],
-        )?;
-        let input = read_csv(input_path.as_path(), &["bits"], b' ')?;
-        Ok(input)
+    use indoc::indoc;
+
+    fn example_input() -> Result<Report> {
+        parse_input(indoc! {"
+            00100
+            11110
+            10110
+            10111
+            10101
+            01111
+            00111
+            11100
+            10000
+            11001
+            00010
+            01010
+        "})
     }
 
     #[test]
@@ -164,19 +182,66 @@ mod tests {
     #[test]
     fn oxygen_co2() -> Result<()> {
         let input = example_input()?;
-        let input = input
-            .iter()
-            .map(|i| i.bits.clone())
-            .collect::<Vec<String>>();
-        assert_eq!(23, get_oxygen(&input));
-        assert_eq!(10, get_co2_scrubber(&input));
+        let mut sorted = input.values.clone();
+        sorted.sort_unstable();
+        assert_eq!(Some(23), rating(&sorted, input.width, BitCriteria::OXYGEN));
+        assert_eq!(
+            Some(10),
+            rating(&sorted, input.width, BitCriteria::CO2_SCRUBBER)
+        );
         Ok(())
     }
 
     #[test]
     fn test_part2() -> Result<()> {
         let input = example_input()?;
-        assert_eq!(230, part2(&input));
+        assert_eq!(230, part2(&input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_column_ones() -> Result<()> {
+        let input = example_input()?;
+        assert_eq!(vec![5, 7, 8, 5, 7], column_ones(&input));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tie_break() {
+        let sorted = [0b00, 0b01, 0b10, 0b11];
+        let criteria = |keep, on_tie| BitCriteria { keep, on_tie };
+        assert_eq!(
+            Some(0b11),
+            rating(&sorted, 2, criteria(Keep::MostCommon, 1))
+        );
+        assert_eq!(
+            Some(0b00),
+            rating(&sorted, 2, criteria(Keep::MostCommon, 0))
+        );
+        assert_eq!(
+            Some(0b11),
+            rating(&sorted, 2, criteria(Keep::LeastCommon, 1))
+        );
+
+        // the least common value of a bit must occur to be kept
+        assert_eq!(
+            Some(0b00),
+            rating(&[0b00, 0b01, 0b01], 2, BitCriteria::CO2_SCRUBBER)
+        );
+        assert_eq!(None, rating(&[], 2, BitCriteria::OXYGEN));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_input("0101\n011").is_err());
+        assert!(parse_input("0121").is_err());
+        assert!(parse_input(&"1".repeat(33)).is_err());
+        assert_eq!(
+            Report {
+                width: 3,
+                values: vec![0b101, 0b001]
+            },
+            parse_input("101\n001\n").unwrap()
+        );
+    }
 }
@@ -1,75 +1,92 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use itertools::Itertools;
-use std::{iter::repeat, path::Path, str::FromStr};
+use std::{path::Path, str::FromStr};
 
-pub fn solve_puzzle(input_path: &Path) -> Result<()> {
+use crate::{input, profile, Solver};
+
+pub fn solve_puzzle(input_path: &Path, rules: &[WinRuleKind]) -> Result<()> {
     let input = std::fs::read_to_string(input_path)?;
     let input = input.split_terminator('\n').collect_vec();
-    println!("Day4, Part1: {}", part1(&input));
-    println!("Day4, Part2: {}", part2(&input));
+    let rules = rules.iter().map(|r| r.rule()).collect_vec();
+    println!(
+        "Day4, Part1: {}",
+        profile::part(4, 1, || part1(&input, &rules))?
+    );
+    println!(
+        "Day4, Part2: {}",
+        profile::part(4, 2, || part2(&input, &rules))?
+    );
     Ok(())
 }
 
-fn part1<T: ToString + AsRef<str>>(input: &[T]) -> u32 {
-    let numbers = NumberPool::from_str(&input[0]);
-    let mut boards = extract_board_numbers(&input[1..])
-        .map(BingoBoard::<5, 5>::from)
-        .collect_vec();
-
-    for number in numbers {
-        boards.iter_mut().for_each(|b| b.mark(number));
-        if let Some(winner) = boards.iter().find(|b| b.is_winner()) {
-            let unmarked_sum = winner
-                .unmarked_numbers()
-                .iter()
-                .map(|n| *n as u32)
-                .reduce(|a, b| a + b)
-                .unwrap_or_default();
-            return unmarked_sum * number as u32;
-        }
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| {
+        Ok(part1(
+            &text.split_terminator('\n').collect_vec(),
+            &ROWS_AND_COLUMNS,
+        )?
+        .to_string())
+    },
+    |text| {
+        Ok(part2(
+            &text.split_terminator('\n').collect_vec(),
+            &ROWS_AND_COLUMNS,
+        )?
+        .to_string())
+    },
+];
+
+/// The rules of the puzzle.
+const ROWS_AND_COLUMNS: [&dyn WinRule; 2] = [&Rows, &Columns];
+
+fn part1<T: ToString + AsRef<str>>(input: &[T], rules: &[&dyn WinRule]) -> Result<u32> {
+    let (numbers, boards) = parse_input(input)?;
+    match simulate(numbers, boards, rules).first() {
+        Some(win) => Ok(win.score),
+        None => bail!("no board wins"),
     }
-    0
 }
 
-fn part2<T: ToString + AsRef<str>>(input: &[T]) -> u32 {
-    let numbers = NumberPool::from_str(&input[0]);
-    let mut boards = extract_board_numbers(&input[1..])
-        .map(BingoBoard::<5, 5>::from)
-        .collect_vec();
-
-    for number in numbers {
-        if boards.len() > 1 {
-            boards.iter_mut().for_each(|b| b.mark(number));
-            boards.retain(|b| !b.is_winner());
-        } else {
-            boards[0].mark(number);
-            let unmarked_sum = boards[0]
-                .unmarked_numbers()
-                .iter()
-                .map(|n| *n as u32)
-                .reduce(|a, b| a + b)
-                .unwrap_or_default();
-            if boards[0].is_winner() {
-                return unmarked_sum * number as u32;
-            }
-        }
+fn part2<T: ToString + AsRef<str>>(input: &[T], rules: &[&dyn WinRule]) -> Result<u32> {
+    let (numbers, boards) = parse_input(input)?;
+    let board_count = boards.len();
+    let wins = simulate(numbers, boards, rules);
+    if wins.len() < board_count {
+        bail!("only {} of {} boards win", wins.len(), board_count);
     }
-    0
+    match wins.last() {
+        Some(win) => Ok(win.score),
+        None => bail!("no board wins"),
+    }
+}
+
+fn parse_input<T: ToString + AsRef<str>>(input: &[T]) -> Result<(NumberPool, Vec<BingoBoard>)> {
+    let (numbers, boards) = match input.split_first() {
+        Some(split) => split,
+        None => bail!("empty input"),
+    };
+    Ok((
+        NumberPool::from_str(numbers.as_ref())?,
+        extract_board_numbers(boards, 2)?,
+    ))
 }
 
 type BingoNumber = u8;
 
 struct NumberPool(Vec<BingoNumber>);
 
-impl NumberPool {
-    pub fn from_str(input: impl AsRef<str>) -> Self {
+impl FromStr for NumberPool {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
         let numbers = input
-            .as_ref()
             .split_terminator(',')
-            .filter_map(|n| BingoNumber::from_str(n.trim()).ok())
-            .rev()
-            .collect();
-        Self(numbers)
+            .map(|n| {
+                BingoNumber::from_str(n.trim()).with_context(|| format!("invalid number '{}'", n))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self(numbers.into_iter().rev().collect()))
     }
 }
 
@@ -116,22 +133,41 @@ impl BoardField {
 }
 
 #[derive(Debug, PartialEq)]
-struct BingoBoard<const C: usize, const R: usize> {
+struct BingoBoard {
+    cols: usize,
+    rows: usize,
     board: Vec<BoardField>,
 }
 
-impl<ITT: IntoIterator<Item = BingoNumber>, const C: usize, const R: usize> From<ITT>
-    for BingoBoard<C, R>
-{
-    fn from(numbers: ITT) -> Self {
-        let numbers = numbers.into_iter().chain(repeat(0)).map(BoardField::from);
-        Self {
-            board: numbers.take(C * R).collect_vec(),
+impl BingoBoard {
+    /// Creates a board from its rows of numbers, which must all be of the same length.
+    pub fn new(rows: Vec<Vec<BingoNumber>>) -> Result<Self> {
+        let cols = rows.first().map(|r| r.len()).unwrap_or_default();
+        if cols == 0 {
+            bail!("empty board");
         }
+        if let Some((r, row)) = rows.iter().find_position(|row| row.len() != cols) {
+            bail!("row {} has {} numbers, expected {}", r + 1, row.len(), cols);
+        }
+        Ok(Self {
+            cols,
+            rows: rows.len(),
+            board: rows.into_iter().flatten().map(BoardField::from).collect(),
+        })
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn is_marked(&self, col: usize, row: usize) -> bool {
+        self.board[row * self.cols + col].is_marked()
     }
-}
 
-impl<const C: usize, const R: usize> BingoBoard<C, R> {
     pub fn mark(&mut self, num: BingoNumber) {
         for field in self.board.iter_mut() {
             if field.number() == num {
@@ -140,32 +176,8 @@ impl<const C: usize, const R: usize> BingoBoard<C, R> {
         }
     }
 
-    pub fn is_winner(&self) -> bool {
-        // check rows
-        for r in 0..R {
-            let x = { 0..C }
-                .map(|c| {
-                    let index = r * C + c;
-                    &self.board[index]
-                })
-                .collect_vec();
-            if x.iter().all(|f| f.is_marked()) {
-                return true;
-            }
-        }
-        // check columns
-        for c in 0..C {
-            let x = { 0..R }
-                .map(|r| {
-                    let index = r * C + c;
-                    &self.board[index]
-                })
-                .collect_vec();
-            if x.iter().all(|f| f.is_marked()) {
-                return true;
-            }
-        }
-        false
+    pub fn is_winner(&self, rules: &[&dyn WinRule]) -> bool {
+        rules.iter().any(|rule| rule.wins(self))
     }
 
     pub fn unmarked_numbers(&self) -> Vec<BingoNumber> {
@@ -179,37 +191,161 @@ impl<const C: usize, const R: usize> BingoBoard<C, R> {
     }
 }
 
-fn extract_board_numbers<T>(input: &[T]) -> impl Iterator<Item = Vec<BingoNumber>> + '_
+impl FromStr for BingoBoard {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let rows = s
+            .lines()
+            .map(input::whitespace_separated)
+            .collect::<Result<Vec<_>>>()?;
+        BingoBoard::new(rows)
+    }
+}
+
+/// Decides whether the marks on a board makes it a winner.
+trait WinRule {
+    fn wins(&self, board: &BingoBoard) -> bool;
+}
+
+/// The win rules to pick from on the command line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WinRuleKind {
+    Rows,
+    Columns,
+    Diagonals,
+    Corners,
+    FullCard,
+}
+
+impl WinRuleKind {
+    fn rule(self) -> &'static dyn WinRule {
+        match self {
+            WinRuleKind::Rows => &Rows,
+            WinRuleKind::Columns => &Columns,
+            WinRuleKind::Diagonals => &Diagonals,
+            WinRuleKind::Corners => &Corners,
+            WinRuleKind::FullCard => &FullCard,
+        }
+    }
+}
+
+impl FromStr for WinRuleKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "rows" => Ok(WinRuleKind::Rows),
+            "columns" => Ok(WinRuleKind::Columns),
+            "diagonals" => Ok(WinRuleKind::Diagonals),
+            "corners" => Ok(WinRuleKind::Corners),
+            "full-card" => Ok(WinRuleKind::FullCard),
+            _ => bail!(
+                "unknown win rule '{}', expected rows, columns, diagonals, corners or full-card",
+                s
+            ),
+        }
+    }
+}
+
+/// Any row fully marked.
+struct Rows;
+
+impl WinRule for Rows {
+    fn wins(&self, board: &BingoBoard) -> bool {
+        (0..board.rows()).any(|r| (0..board.cols()).all(|c| board.is_marked(c, r)))
+    }
+}
+
+/// Any column fully marked.
+struct Columns;
+
+impl WinRule for Columns {
+    fn wins(&self, board: &BingoBoard) -> bool {
+        (0..board.cols()).any(|c| (0..board.rows()).all(|r| board.is_marked(c, r)))
+    }
+}
+
+/// Any of the two diagonals fully marked; only square boards have diagonals.
+struct Diagonals;
+
+impl WinRule for Diagonals {
+    fn wins(&self, board: &BingoBoard) -> bool {
+        let n = board.cols();
+        n == board.rows()
+            && ((0..n).all(|i| board.is_marked(i, i))
+                || (0..n).all(|i| board.is_marked(n - 1 - i, i)))
+    }
+}
+
+/// All four corners marked.
+struct Corners;
+
+impl WinRule for Corners {
+    fn wins(&self, board: &BingoBoard) -> bool {
+        let (c, r) = (board.cols() - 1, board.rows() - 1);
+        [(0, 0), (c, 0), (0, r), (c, r)]
+            .into_iter()
+            .all(|(c, r)| board.is_marked(c, r))
+    }
+}
+
+/// Every number on the board marked.
+struct FullCard;
+
+impl WinRule for FullCard {
+    fn wins(&self, board: &BingoBoard) -> bool {
+        board.board.iter().all(|f| f.is_marked())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Win {
+    /// Index of the winning board.
+    board: usize,
+    /// Turn the board won in, counting from 1.
+    turn: usize,
+    /// The number that made the board win.
+    number: BingoNumber,
+    /// Sum of the unmarked numbers multiplied by `number`.
+    score: u32,
+}
+
+/// Draws the numbers until they run out, returning the wins in the order they happen.
+///
+/// A board wins once, and isn't marked any further after it has won.
+fn simulate(numbers: NumberPool, boards: Vec<BingoBoard>, rules: &[&dyn WinRule]) -> Vec<Win> {
+    let mut playing = boards.into_iter().enumerate().collect_vec();
+    let mut wins = vec![];
+    for (turn, number) in numbers.enumerate() {
+        playing.iter_mut().for_each(|(_, b)| b.mark(number));
+        playing.retain(|(board, b)| {
+            if !b.is_winner(rules) {
+                return true;
+            }
+            let unmarked_sum = b.unmarked_numbers().iter().map(|n| *n as u32).sum::<u32>();
+            wins.push(Win {
+                board: *board,
+                turn: turn + 1,
+                number,
+                score: unmarked_sum * number as u32,
+            });
+            false
+        });
+        if playing.is_empty() {
+            break;
+        }
+    }
+    wins
+}
+
+/// Reads the boards separated by blank lines. Errors refer to line numbers of the input file, in
+/// which `input` starts at line `first_line`.
+fn extract_board_numbers<T>(input: &[T], first_line: usize) -> Result<Vec<BingoBoard>>
 where
     T: ToString,
 {
-    let itt = input.iter().map(|x| x.to_string()).batching(|it| {
-        use itertools::FoldWhile::{Continue, Done};
-        let numbers = it
-            .skip_while(|l| l.trim().is_empty())
-            .fold_while(vec![], |mut acc, l| {
-                if l.trim().is_empty() {
-                    Done(acc)
-                } else {
-                    acc.push(l);
-                    Continue(acc)
-                }
-            })
-            .into_inner();
-        if numbers.is_empty() {
-            None
-        } else {
-            Some(
-                numbers
-                    .into_iter()
-                    .join(" ")
-                    .split_whitespace()
-                    .filter_map(|n| BingoNumber::from_str(n.trim()).ok())
-                    .collect_vec(),
-            )
-        }
-    });
-    itt
+    input::blocks(&input.iter().map(|l| l.to_string()).join("\n"), first_line)
 }
 
 #[cfg(test)]
@@ -247,7 +383,7 @@ mod tests {
     #[test]
     fn numbers_pool() {
         let input = &example_input()[0];
-        let pool = NumberPool::from_str(input);
+        let pool = NumberPool::from_str(input).unwrap();
         assert_eq!(
             vec![
                 7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24, 10, 16, 13, 6, 15, 25, 12, 22, 18, 20, 8,
@@ -260,14 +396,84 @@ mod tests {
     #[test]
     fn test_part1() {
         let input = example_input();
-        let result = part1(&input);
+        let result = part1(&input, &ROWS_AND_COLUMNS).unwrap();
         assert_eq!(4512, result);
     }
 
     #[test]
     fn test_part2() {
         let input = example_input();
-        let result = part2(&input);
+        let result = part2(&input, &ROWS_AND_COLUMNS).unwrap();
         assert_eq!(1924, result);
     }
+
+    #[test]
+    fn test_win_order() {
+        let (numbers, boards) = parse_input(&example_input()).unwrap();
+        let wins = simulate(numbers, boards, &ROWS_AND_COLUMNS);
+        assert_eq!(
+            vec![(2, 12, 24), (0, 14, 16), (1, 15, 13)],
+            wins.iter()
+                .map(|w| (w.board, w.turn, w.number))
+                .collect_vec()
+        );
+        assert_eq!(1924, wins[2].score);
+    }
+
+    #[test]
+    fn test_win_rules() {
+        let board = || BingoBoard::new(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]).unwrap();
+        let play = |numbers: &str, rule: &dyn WinRule| {
+            simulate(numbers.parse().unwrap(), vec![board()], &[rule])
+                .first()
+                .map(|w| w.turn)
+        };
+        assert_eq!(Some(3), play("1,5,9,2", &Diagonals));
+        assert_eq!(Some(3), play("3,5,7,2", &Diagonals));
+        assert_eq!(None, play("1,2,3,4,6", &Diagonals));
+        assert_eq!(Some(4), play("1,3,7,9", &Corners));
+        assert_eq!(Some(3), play("2,5,8", &Columns));
+        assert_eq!(None, play("2,5,8", &Rows));
+        assert_eq!(Some(9), play("9,8,7,6,5,4,3,2,1", &FullCard));
+
+        let rules = "rows,full-card"
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<WinRuleKind>>>();
+        assert_eq!(
+            vec![WinRuleKind::Rows, WinRuleKind::FullCard],
+            rules.unwrap()
+        );
+        assert!("row".parse::<WinRuleKind>().is_err());
+        let input = example_input();
+        let diagonals = [WinRuleKind::Diagonals.rule()];
+        assert_eq!(494, part1(&input, &diagonals).unwrap());
+        assert_eq!(76, part2(&input, &diagonals).unwrap());
+    }
+
+    #[test]
+    fn test_board_size_from_input() {
+        let input = ["1,2,3,4", "", "1 2", "3 4", "", "4 3 2", "1 9 9"];
+        let (numbers, boards) = parse_input(&input).unwrap();
+        assert_eq!((2, 2), (boards[0].cols(), boards[0].rows()));
+        assert_eq!((3, 2), (boards[1].cols(), boards[1].rows()));
+        let wins = simulate(numbers, boards, &ROWS_AND_COLUMNS);
+        assert_eq!(
+            vec![(0, 2), (1, 4)],
+            wins.iter().map(|w| (w.board, w.turn)).collect_vec()
+        );
+    }
+
+    #[test]
+    fn test_malformed_boards() {
+        assert!(extract_board_numbers(&["1 2", "3"], 1).is_err());
+        assert!(extract_board_numbers(&["1 2", "3 x"], 1).is_err());
+        assert!(extract_board_numbers(&["1 2", "3 256"], 1).is_err());
+        let err = parse_input(&["1,2", "", "1 2", "3 4", "", "5 6", "x 8"])
+            .err()
+            .unwrap();
+        assert_eq!("block at line 6", err.to_string());
+        assert!(parse_input(&["1,x", "", "1"]).is_err());
+        assert!(parse_input::<&str>(&[]).is_err());
+    }
 }
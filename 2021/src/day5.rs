@@ -1,25 +1,48 @@
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use derive_more::{Add, Constructor, Sub};
-use image::{Rgb, RgbImage};
+use image::{GrayImage, Luma};
 use itertools::Itertools;
-use regex::Regex;
-use std::path::Path;
-use std::str::FromStr;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, space0, u32 as uint32},
+    combinator::{all_consuming, map},
+    sequence::{delimited, separated_pair},
+    IResult,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+use crate::{profile, Solver};
+use crate::visualize::{Ramp, Visualizer};
 
 pub fn solve_puzzle(input_path: &Path) -> Result<()> {
     let input = std::fs::read_to_string(input_path)?;
-    let input = parse_input(&input);
-    println!("Day5, Part1: {}", part1(&input));
-    println!("Day5, Part2: {}", part2(&input));
+    let input = parse_input(&input)?;
+    println!(
+        "Day5, Part1: {}",
+        profile::part(5, 1, || Ok(part1(&input)))?
+    );
+    println!(
+        "Day5, Part2: {}",
+        profile::part(5, 2, || part2(&input, Visualizer::new("day5_heatmap")?))?
+    );
     Ok(())
 }
 
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(&parse_input(text)?).to_string()),
+    |text| Ok(part2(&parse_input(text)?, Visualizer::new("day5_heatmap")?)?.to_string()),
+];
+
 type PuzzleInput = Line;
 
-#[derive(Debug, PartialEq, PartialOrd, Add, Sub, Constructor, Clone, Copy, Default)]
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Add, Sub, Constructor, Clone, Copy, Default)]
 struct Point {
-    x: i32,
-    y: i32,
+    x: i64,
+    y: i64,
 }
 
 #[derive(Debug, PartialEq, Constructor, Clone, Copy)]
@@ -28,34 +51,274 @@ struct Line {
     p2: Point,
 }
 
-fn parse_input(input: &str) -> Vec<PuzzleInput> {
-    let re = Regex::new(r"(?P<x1>\d+),(?P<y1>\d+)\s*->\s*(?P<x2>\d+),(?P<y2>\d+)").unwrap();
+/// Which lines are drawn, and which are skipped.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Rasterizer {
+    /// Horizontal and vertical lines only.
+    AxisAligned,
+    /// Horizontal, vertical and 45° diagonal lines.
+    Diagonal,
+}
+
+/// Direction of a drawable line. Lines in the same direction only share points when they lie on
+/// the same infinite line, lines in different directions share a single point at most.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+enum Direction {
+    Horizontal,
+    Vertical,
+    /// x and y grow together.
+    Diagonal,
+    /// y shrinks as x grows.
+    AntiDiagonal,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::Horizontal,
+        Direction::Vertical,
+        Direction::Diagonal,
+        Direction::AntiDiagonal,
+    ];
+
+    /// `(a, b)` where `a * x + b * y` is the same for every point of a line in this direction.
+    fn normal(self) -> (i64, i64) {
+        match self {
+            Direction::Horizontal => (0, 1),
+            Direction::Vertical => (1, 0),
+            Direction::Diagonal => (-1, 1),
+            Direction::AntiDiagonal => (1, 1),
+        }
+    }
+
+    /// Step from one point to the next along a line in this direction.
+    fn step(self) -> (i64, i64) {
+        match self {
+            Direction::Horizontal => (1, 0),
+            Direction::Vertical => (0, 1),
+            Direction::Diagonal => (1, 1),
+            Direction::AntiDiagonal => (1, -1),
+        }
+    }
+
+    /// Which of the lines in this direction `p` lies on.
+    fn offset(self, p: Point) -> i64 {
+        let (a, b) = self.normal();
+        a * p.x + b * p.y
+    }
+
+    /// Where `p` lies along a line in this direction.
+    fn position(self, p: Point) -> i64 {
+        match self {
+            Direction::Vertical => p.y,
+            _ => p.x,
+        }
+    }
+
+    fn point(self, offset: i64, position: i64) -> Point {
+        match self {
+            Direction::Horizontal => Point::new(position, offset),
+            Direction::Vertical => Point::new(offset, position),
+            Direction::Diagonal => Point::new(position, offset + position),
+            Direction::AntiDiagonal => Point::new(position, offset - position),
+        }
+    }
+}
+
+/// The positions `start..=end` along the line `offset` in `direction`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Span {
+    direction: Direction,
+    offset: i64,
+    start: i64,
+    end: i64,
+}
+
+impl Line {
+    fn direction(&self) -> Option<Direction> {
+        let d = self.p2 - self.p1;
+        match (d.x, d.y) {
+            (_, 0) => Some(Direction::Horizontal),
+            (0, _) => Some(Direction::Vertical),
+            (x, y) if x == y => Some(Direction::Diagonal),
+            (x, y) if x == -y => Some(Direction::AntiDiagonal),
+            _ => None,
+        }
+    }
+
+    /// The points covered by the line, or `None` if the rasterizer skips the line.
+    fn rasterize(&self, rasterizer: Rasterizer) -> Option<Span> {
+        let direction = self.direction()?;
+        let drawn = match direction {
+            Direction::Horizontal | Direction::Vertical => true,
+            Direction::Diagonal | Direction::AntiDiagonal => rasterizer == Rasterizer::Diagonal,
+        };
+        let (a, b) = (direction.position(self.p1), direction.position(self.p2));
+        drawn.then(|| Span {
+            direction,
+            offset: direction.offset(self.p1),
+            start: a.min(b),
+            end: a.max(b),
+        })
+    }
+}
+
+/// Positions `start..=end` covered by the same number of lines.
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Run {
+    start: i64,
+    end: i64,
+    lines: u32,
+}
+
+impl Run {
+    fn len(&self) -> i64 {
+        self.end - self.start + 1
+    }
+}
+
+/// Counts how many lines cover each point, sweeping along every infinite line the segments lie
+/// on. Time and memory depend on the number of segments, not on their lengths.
+#[derive(Debug, Default)]
+struct Overlaps {
+    /// The covered runs of each line, ordered by position.
+    runs: HashMap<(Direction, i64), Vec<Run>>,
+}
+
+impl Overlaps {
+    fn from_lines(lines: &[Line], rasterizer: Rasterizer) -> Self {
+        let mut events = HashMap::<_, Vec<(i64, i64)>>::new();
+        for span in lines.iter().filter_map(|l| l.rasterize(rasterizer)) {
+            let events = events.entry((span.direction, span.offset)).or_default();
+            events.push((span.start, 1));
+            events.push((span.end + 1, -1));
+        }
+
+        let mut overlaps = Self::default();
+        for (line, mut events) in events {
+            events.sort_unstable();
+            let mut runs = Vec::new();
+            let mut lines = 0;
+            for (i, &(position, delta)) in events.iter().enumerate() {
+                lines += delta;
+                match events.get(i + 1) {
+                    Some(&(next, _)) if next > position && lines > 0 => runs.push(Run {
+                        start: position,
+                        end: next - 1,
+                        lines: lines as u32,
+                    }),
+                    _ => {}
+                }
+            }
+            overlaps.runs.insert(line, runs);
+        }
+        overlaps
+    }
+
+    /// Number of lines in `direction` covering `p`.
+    fn lines_at(&self, direction: Direction, p: Point) -> u32 {
+        let position = direction.position(p);
+        self.runs
+            .get(&(direction, direction.offset(p)))
+            .and_then(|runs| runs.get(runs.partition_point(|r| r.end < position)))
+            .filter(|r| r.start <= position)
+            .map_or(0, |r| r.lines)
+    }
+
+    fn spans(&self) -> impl Iterator<Item = (Span, u32)> + '_ {
+        self.runs.iter().flat_map(|(&(direction, offset), runs)| {
+            runs.iter().map(move |r| {
+                let span = Span {
+                    direction,
+                    offset,
+                    start: r.start,
+                    end: r.end,
+                };
+                (span, r.lines)
+            })
+        })
+    }
+
+    /// The points covered by lines in more than one direction.
+    fn crossings(&self) -> HashSet<Point> {
+        let spans = self.spans().map(|(span, _)| span).collect_vec();
+        spans
+            .iter()
+            .tuple_combinations()
+            .filter(|(a, b)| a.direction != b.direction)
+            .filter_map(|(a, b)| {
+                let p = intersection(a, b)?;
+                [a, b]
+                    .iter()
+                    .all(|s| (s.start..=s.end).contains(&s.direction.position(p)))
+                    .then_some(p)
+            })
+            .collect()
+    }
+
+    /// Number of points covered by at least `lines` lines.
+    ///
+    /// Points covered in a single direction are counted from the runs, the few points where
+    /// directions cross are counted one by one.
+    fn count(&self, lines: u32) -> usize {
+        let mut count: i64 = self
+            .runs
+            .values()
+            .flatten()
+            .filter(|r| r.lines >= lines)
+            .map(Run::len)
+            .sum();
+        for p in self.crossings() {
+            let per_direction = Direction::ALL.map(|d| self.lines_at(d, p));
+            count -= per_direction.iter().filter(|&&n| n >= lines).count() as i64;
+            if per_direction.iter().sum::<u32>() >= lines {
+                count += 1;
+            }
+        }
+        count as usize
+    }
+}
+
+/// The point shared by the infinite lines of `a` and `b`, if it lies on the grid.
+fn intersection(a: &Span, b: &Span) -> Option<Point> {
+    let ((a1, b1), (a2, b2)) = (a.direction.normal(), b.direction.normal());
+    let det = a1 * b2 - b1 * a2;
+    let x = a.offset * b2 - b1 * b.offset;
+    let y = a1 * b.offset - a.offset * a2;
+    (det != 0 && x % det == 0 && y % det == 0).then(|| Point::new(x / det, y / det))
+}
+
+fn parse_input(input: &str) -> Result<Vec<PuzzleInput>> {
     input
-        .split_terminator('\n')
-        .map(|l| {
-            let captures = re.captures(l).unwrap();
-            let x1 = captures
-                .name("x1")
-                .map_or(0, |m| i32::from_str(m.as_str()).unwrap());
-            let y1 = captures
-                .name("y1")
-                .map_or(0, |m| i32::from_str(m.as_str()).unwrap());
-            let x2 = captures
-                .name("x2")
-                .map_or(0, |m| i32::from_str(m.as_str()).unwrap());
-            let y2 = captures
-                .name("y2")
-                .map_or(0, |m| i32::from_str(m.as_str()).unwrap());
-            Line::new(Point::new(x1, y1), Point::new(x2, y2))
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(n, l)| {
+            all_consuming(line)(l.trim())
+                .map(|(_, line)| line)
+                .map_err(|e| anyhow!(e.to_string()))
+                .with_context(|| format!("line {}: '{}'", n + 1, l))
         })
-        .collect_vec()
+        .collect()
+}
+
+fn point(input: &str) -> IResult<&str, Point> {
+    map(separated_pair(uint32, char(','), uint32), |(x, y)| {
+        Point::new(x as i64, y as i64)
+    })(input)
+}
+
+fn line(input: &str) -> IResult<&str, Line> {
+    map(
+        separated_pair(point, delimited(space0, tag("->"), space0), point),
+        |(p1, p2)| Line::new(p1, p2),
+    )(input)
 }
 
 #[derive(Debug)]
 struct HeatMap {
     cols: usize,
     rows: usize,
-    map: Vec<u32>,
+    map: Vec<u64>,
 }
 
 impl HeatMap {
@@ -63,7 +326,7 @@ impl HeatMap {
         Self {
             cols,
             rows,
-            map: Vec::from_iter(std::iter::repeat(0).take((cols * rows) as usize)),
+            map: vec![0; cols * rows],
         }
     }
 
@@ -74,41 +337,25 @@ impl HeatMap {
         self.rows
     }
 
-    fn get_xy(&self, x: usize, y: usize) -> Option<&u32> {
+    fn get_xy(&self, x: usize, y: usize) -> Option<&u64> {
         if x >= self.cols || y >= self.rows {
             return None;
         }
         Some(&self.map[x + y * self.cols])
     }
 
-    fn iter(&self) -> impl Iterator<Item = &u32> {
+    fn iter(&self) -> impl Iterator<Item = &u64> {
         self.map.iter()
     }
 
-    fn mark(&mut self, p: Point) -> u32 {
+    fn add(&mut self, p: Point, value: u64) -> u64 {
         if p.x < 0 || p.x as usize >= self.cols || p.y < 0 || p.y as usize >= self.rows {
             return 0;
         }
 
-        let idx = p.x + p.y * self.rows as i32;
-        self.map[idx as usize] += 1;
-        self.map[idx as usize]
-    }
-
-    fn generate_minimap(&self, cols: usize, rows: usize) -> HeatMap {
-        let cols = cols.min(self.cols);
-        let rows = rows.min(self.rows);
-        let mut minimap = HeatMap::new(cols, rows);
-
-        for y in 0..self.rows {
-            let minimap_y = y * rows / self.rows;
-            for x in 0..self.cols {
-                let minimap_x = x * cols / self.cols;
-                let value = self.map[x + y * self.cols];
-                minimap.map[minimap_x + minimap_y * cols] += value;
-            }
-        }
-        minimap
+        let idx = p.x as usize + p.y as usize * self.cols;
+        self.map[idx] += value;
+        self.map[idx]
     }
 }
 
@@ -124,94 +371,92 @@ impl std::fmt::Display for HeatMap {
     }
 }
 
-fn part1(input: &[PuzzleInput]) -> u32 {
-    // find heatmap size
-    let (x_max, y_max) = input
-        .iter()
-        .map(|l| (l.p1.x.max(l.p2.x), l.p1.y.max(l.p2.y)))
-        .reduce(|a, b| (a.0.max(b.0), a.1.max(b.1)))
-        .unwrap_or_default();
-
-    // create heatmap
-    let mut heatmap = HeatMap::new((x_max + 1) as usize, (y_max + 1) as usize);
-    for line in input {
-        if line.p1.y == line.p2.y {
-            // horizontal
-            let (x1, x2) = (line.p1.x.min(line.p2.x), line.p1.x.max(line.p2.x));
-            for x in x1..=x2 {
-                heatmap.mark(Point::new(x, line.p1.y));
-            }
-        } else if line.p1.x == line.p2.x {
-            // vertical
-            let (y1, y2) = (line.p1.y.min(line.p2.y), line.p1.y.max(line.p2.y));
-            for y in y1..=y2 {
-                heatmap.mark(Point::new(line.p1.x, y));
-            }
-        }
-    }
+fn part1(input: &[PuzzleInput]) -> usize {
+    Overlaps::from_lines(input, Rasterizer::AxisAligned).count(2)
+}
+
+fn part2(input: &[PuzzleInput], viz: Visualizer) -> Result<usize> {
+    let overlaps = Overlaps::from_lines(input, Rasterizer::Diagonal);
+
+    visualize(&overlaps, viz)?;
 
-    heatmap.iter().filter(|&&h| h > 1).count() as u32
+    Ok(overlaps.count(2))
 }
 
-fn part2(input: &[PuzzleInput]) -> u32 {
-    // find heatmap size
-    let (x_max, y_max) = input
-        .iter()
-        .map(|l| (l.p1.x.max(l.p2.x), l.p1.y.max(l.p2.y)))
-        .reduce(|a, b| (a.0.max(b.0), a.1.max(b.1)))
-        .unwrap_or_default();
-
-    // create heatmap
-    let mut heatmap = HeatMap::new((x_max + 1) as usize, (y_max + 1) as usize);
-    for line in input {
-        if line.p1.y == line.p2.y {
-            // horizontal
-            let (x1, x2) = (line.p1.x.min(line.p2.x), line.p1.x.max(line.p2.x));
-            for x in x1..=x2 {
-                heatmap.mark(Point::new(x, line.p1.y));
-            }
-        } else if line.p1.x == line.p2.x {
-            // vertical
-            let (y1, y2) = (line.p1.y.min(line.p2.y), line.p1.y.max(line.p2.y));
-            for y in y1..=y2 {
-                heatmap.mark(Point::new(line.p1.x, y));
-            }
-        } else {
-            // diagonal
-            let (p1, p2) = if line.p1.x <= line.p2.x {
-                (line.p1, line.p2)
-            } else {
-                (line.p2, line.p1)
-            };
-            let dy = (p2.y - p1.y) as i32 / (p2.x - p1.x) as i32;
-            let mut y = p1.y;
-            for x in p1.x..=p2.x {
-                heatmap.mark(Point::new(x, y));
-                y += dy;
-            }
+fn visualize(overlaps: &Overlaps, mut viz: Visualizer) -> Result<()> {
+    if !viz.is_enabled() {
+        return Ok(());
+    }
+    let heatmap = downsample(overlaps, 250, 250);
+    let high_value = heatmap.iter().copied().max().unwrap_or_default().max(1);
+    let mut img = GrayImage::new(heatmap.cols() as u32, heatmap.rows() as u32);
+    for y in 0..heatmap.rows() {
+        for x in 0..heatmap.cols() {
+            let value = *heatmap.get_xy(x, y).unwrap();
+            img.put_pixel(x as u32, y as u32, Luma([(value * 255 / high_value) as u8]));
         }
     }
+    viz.grid(&img, &Ramp::heat(255))?;
+    viz.finish()
+}
+
+/// A heatmap of the covered area, at most `max_cols` by `max_rows` cells. Every cell adds up the
+/// lines covering the points of a block of the area.
+fn downsample(overlaps: &Overlaps, max_cols: i64, max_rows: i64) -> HeatMap {
+    let ends = overlaps.spans().flat_map(|(s, _)| {
+        [
+            s.direction.point(s.offset, s.start),
+            s.direction.point(s.offset, s.end),
+        ]
+    });
+    let Some((min, max)) = ends.fold(None, |bounds, p| match bounds {
+        None => Some((p, p)),
+        Some((min, max)) => Some((
+            Point::new(p.x.min(min.x), p.y.min(min.y)),
+            Point::new(p.x.max(max.x), p.y.max(max.y)),
+        )),
+    }) else {
+        return HeatMap::new(0, 0);
+    };
+    let size = max - min + Point::new(1, 1);
+    let block = Point::new(
+        (size.x + max_cols - 1) / max_cols,
+        (size.y + max_rows - 1) / max_rows,
+    );
+    let mut heatmap = HeatMap::new(
+        ((size.x + block.x - 1) / block.x) as usize,
+        ((size.y + block.y - 1) / block.y) as usize,
+    );
 
-    // generate heatmap image!
-    let minimap = heatmap.generate_minimap(250, 250);
-    let high_value = (*minimap.iter().max().unwrap()).max(1);
-    let mut img = RgbImage::new(minimap.cols() as u32, minimap.rows() as u32);
-    for y in 0..minimap.rows() {
-        for x in 0..minimap.cols() {
-            let value = *minimap.get_xy(x, y).unwrap();
-            let value = (value * 255 / high_value) as u8;
-            img.put_pixel(x as u32, y as u32, Rgb([value, value, value]));
+    // walk every run a block at a time
+    for (span, lines) in overlaps.spans() {
+        let (dx, dy) = span.direction.step();
+        let mut position = span.start;
+        while position <= span.end {
+            let p = span.direction.point(span.offset, position) - min;
+            let cell = Point::new(p.x / block.x, p.y / block.y);
+            let mut steps = span.end - position + 1;
+            if dx > 0 {
+                steps = steps.min((cell.x + 1) * block.x - p.x);
+            }
+            match dy {
+                1 => steps = steps.min((cell.y + 1) * block.y - p.y),
+                -1 => steps = steps.min(p.y - cell.y * block.y + 1),
+                _ => {}
+            }
+            heatmap.add(cell, lines as u64 * steps as u64);
+            position += steps;
         }
     }
-    img.save("heatmap.png").unwrap();
-
-    heatmap.iter().filter(|&&h| h > 1).count() as u32
+    heatmap
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::visualize::golden;
     use indoc::indoc;
+    use itertools::Itertools;
 
     fn example_input() -> String {
         let input = indoc! {"
@@ -232,7 +477,7 @@ mod tests {
     #[test]
     fn test_input() {
         let input = example_input();
-        let input = parse_input(&input);
+        let input = parse_input(&input).unwrap();
         assert_eq!(
             &vec![Line::new(Point::new(0, 9), Point::new(5, 9)),][0],
             &input[0]
@@ -242,7 +487,7 @@ mod tests {
     #[test]
     fn test_part1() {
         let input = example_input();
-        let input = parse_input(&input);
+        let input = parse_input(&input).unwrap();
         let result = part1(&input);
         assert_eq!(5, result);
     }
@@ -250,8 +495,158 @@ mod tests {
     #[test]
     fn example_part2() {
         let input = example_input();
-        let input = parse_input(&input);
-        let result = part2(&input);
+        let input = parse_input(&input).unwrap();
+        let result = part2(&input, golden::disabled()).unwrap();
         assert_eq!(12, result);
     }
+
+    #[test]
+    fn golden_heatmap() -> Result<()> {
+        let input = parse_input(&example_input())?;
+        let (viz, frames) = golden::recorder();
+        part2(&input, viz)?;
+        let frames = frames.borrow();
+        golden::assert_golden("day5_heatmap", &frames[0])
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_input("0,9 -> 5").is_err());
+        assert!(parse_input("0,9 -> 5,9 -> 1,1").is_err());
+        assert!(parse_input("0,-9 -> 5,9").is_err());
+        assert!(parse_input("0,9 => 5,9").is_err());
+        assert_eq!(1, parse_input("\n 0,9->5,9 \n\n").unwrap().len());
+    }
+
+    #[test]
+    fn test_rasterize() {
+        let span = |x1, y1, x2, y2, rasterizer| {
+            Line::new(Point::new(x1, y1), Point::new(x2, y2))
+                .rasterize(rasterizer)
+                .map(|s| {
+                    (s.start..=s.end)
+                        .map(|position| s.direction.point(s.offset, position))
+                        .map(|p| (p.x, p.y))
+                        .collect_vec()
+                })
+        };
+        assert_eq!(
+            Some(vec![(1, 1), (2, 1), (3, 1)]),
+            span(3, 1, 1, 1, Rasterizer::AxisAligned)
+        );
+        assert_eq!(
+            Some(vec![(1, 1), (1, 2), (1, 3)]),
+            span(1, 1, 1, 3, Rasterizer::AxisAligned)
+        );
+        assert_eq!(None, span(9, 7, 7, 9, Rasterizer::AxisAligned));
+        assert_eq!(
+            Some(vec![(7, 9), (8, 8), (9, 7)]),
+            span(9, 7, 7, 9, Rasterizer::Diagonal)
+        );
+        assert_eq!(
+            Some(vec![(2, 3), (3, 4)]),
+            span(3, 4, 2, 3, Rasterizer::Diagonal)
+        );
+        assert_eq!(None, span(0, 0, 4, 2, Rasterizer::Diagonal));
+        assert_eq!(Some(vec![(5, 5)]), span(5, 5, 5, 5, Rasterizer::Diagonal));
+    }
+
+    /// Counts the overlaps point by point.
+    fn brute_force(lines: &[Line], rasterizer: Rasterizer, min_lines: u32) -> usize {
+        lines
+            .iter()
+            .filter_map(|l| l.rasterize(rasterizer))
+            .flat_map(|s| (s.start..=s.end).map(move |pos| s.direction.point(s.offset, pos)))
+            .counts()
+            .values()
+            .filter(|&&n| n >= min_lines as usize)
+            .count()
+    }
+
+    #[test]
+    fn test_overlaps_match_brute_force() {
+        let input = parse_input(&example_input()).unwrap();
+        // crossings off the grid, crossings at the ends, and three directions through one point
+        let crossing = parse_input(indoc! {"
+            0,0 -> 3,3
+            0,3 -> 3,0
+            2,0 -> 2,5
+            0,4 -> 4,4
+            0,4 -> 4,0
+            1,1 -> 1,1
+        "})
+        .unwrap();
+        for input in [input, crossing] {
+            for rasterizer in [Rasterizer::AxisAligned, Rasterizer::Diagonal] {
+                let overlaps = Overlaps::from_lines(&input, rasterizer);
+                for min_lines in 1..=4 {
+                    assert_eq!(
+                        brute_force(&input, rasterizer, min_lines),
+                        overlaps.count(min_lines),
+                        "{:?}, at least {} lines",
+                        rasterizer,
+                        min_lines
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_huge_coordinates() {
+        let input = parse_input(indoc! {"
+            4000000000,7 -> 4000000003,7
+            4000000002,5 -> 4000000002,9
+        "})
+        .unwrap();
+        assert_eq!(1, part1(&input));
+
+        // more points than fit in a u32
+        let input = parse_input(indoc! {"
+            0,0 -> 4000000000,0
+            4000000000,0 -> 0,0
+            0,1 -> 4000000000,1
+            4000000000,1 -> 0,1
+        "})
+        .unwrap();
+        assert_eq!(8_000_000_002, part1(&input));
+
+        let input = parse_input(indoc! {"
+            0,0 -> 4000000000,0
+            0,0 -> 0,4000000000
+            0,4000000000 -> 4000000000,0
+            10,0 -> 3000000000,0
+        "})
+        .unwrap();
+        let (viz, frames) = golden::recorder();
+        assert_eq!(2_999_999_994, part2(&input, viz).unwrap());
+        let frames = frames.borrow();
+        assert_eq!((250, 250), frames[0].dimensions());
+    }
+
+    #[test]
+    fn test_downsample() {
+        let input = parse_input(indoc! {"
+            0,0 -> 9,0
+            0,0 -> 9,9
+            9,0 -> 0,9
+        "})
+        .unwrap();
+        let heatmap = downsample(&Overlaps::from_lines(&input, Rasterizer::Diagonal), 4, 2);
+        assert_eq!((4, 2), (heatmap.cols(), heatmap.rows()));
+        assert_eq!(30, heatmap.iter().sum::<u64>());
+        assert_eq!("6\t6\t6\t2\t\n3\t3\t3\t1\t\n", heatmap.to_string());
+    }
+
+    #[test]
+    fn test_heatmap_not_square() {
+        let mut heatmap = HeatMap::new(4, 2);
+        heatmap.add(Point::new(3, 1), 1);
+        heatmap.add(Point::new(0, 1), 1);
+        heatmap.add(Point::new(0, 1), 1);
+        assert_eq!(Some(&2), heatmap.get_xy(0, 1));
+        assert_eq!(Some(&1), heatmap.get_xy(3, 1));
+        assert_eq!(3, heatmap.iter().sum::<u64>());
+        assert_eq!("0\t0\t0\t0\t\n2\t0\t0\t1\t\n", heatmap.to_string());
+    }
 }
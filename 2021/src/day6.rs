@@ -1,61 +1,319 @@
-use anyhow::Result;
-use std::{fs::read_to_string, path::Path};
+use anyhow::{bail, Context, Result};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::{
+    fs::read_to_string,
+    ops::{Add, Mul},
+    path::Path,
+};
 
-const MAX_AGE: usize = 9;
+use crate::{profile, Solver};
 
-pub fn solve_puzzle(input_path: &Path) -> Result<()> {
-    let input = parse_input(&read_to_string(input_path)?);
-    println!("Day6, Part1: {}", part1(input.clone()));
-    println!("Day6, Part2: {}", part2(input));
+/// Solves the puzzle for fish spawning every `spawn_interval` days, and `maturity` days later when
+/// newborn; lanternfish spawn every 7 days, and 2 days later when newborn.
+pub fn solve_puzzle(input_path: &Path, spawn_interval: usize, maturity: usize) -> Result<()> {
+    let life_cycle = LifeCycle::new(spawn_interval, maturity)?;
+    let input = parse_input(&read_to_string(input_path)?, &life_cycle)?;
+    println!(
+        "Day6, Part1: {}",
+        profile::part(6, 1, || Ok(part1(&input, &life_cycle)))?
+    );
+    println!(
+        "Day6, Part2: {}",
+        profile::part(6, 2, || Ok(part2(&input, &life_cycle)))?
+    );
     Ok(())
 }
 
-fn part1(mut input: Box<[u64]>) -> u64 {
-    simulate(&mut input, 80)
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| {
+        Ok(part1(
+            &parse_input(text, &LifeCycle::LANTERNFISH)?,
+            &LifeCycle::LANTERNFISH,
+        )
+        .to_string())
+    },
+    |text| {
+        Ok(part2(
+            &parse_input(text, &LifeCycle::LANTERNFISH)?,
+            &LifeCycle::LANTERNFISH,
+        )
+        .to_string())
+    },
+];
+
+/// The fish are counted without bounds, as short life cycles outgrow any fixed width in 256 days.
+fn big_counts(input: &[u64]) -> Vec<BigUint> {
+    input.iter().copied().map(BigUint::from).collect()
+}
+
+fn part1(input: &[u64], life_cycle: &LifeCycle) -> BigUint {
+    simulate(&mut big_counts(input), life_cycle, 80)
 }
 
-fn part2(mut input: Box<[u64]>) -> u64 {
-    simulate(&mut input, 256)
+fn part2(input: &[u64], life_cycle: &LifeCycle) -> BigUint {
+    fast_forward(&big_counts(input), life_cycle, 256)
 }
 
-fn parse_input(input: &str) -> Box<[u64]> {
-    let mut fish_by_spawn_delay = Box::new([0; MAX_AGE]);
-    for age in input
-        .split(',')
-        .map(|s| s.parse::<u64>().expect("unable to parse age"))
-    {
-        fish_by_spawn_delay[age as usize % MAX_AGE] += 1;
+/// Timers of a fish species.
+///
+/// An adult spawns a new fish every `spawn_interval` days, while a newborn needs `maturity` extra
+/// days before it spawns for the first time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LifeCycle {
+    spawn_interval: usize,
+    maturity: usize,
+}
+
+impl LifeCycle {
+    const LANTERNFISH: LifeCycle = LifeCycle {
+        spawn_interval: 7,
+        maturity: 2,
+    };
+
+    /// A life cycle spawning every `spawn_interval` days, which has to be at least one.
+    fn new(spawn_interval: usize, maturity: usize) -> Result<Self> {
+        if spawn_interval == 0 {
+            bail!("fish can't spawn every 0 days");
+        }
+        Ok(Self {
+            spawn_interval,
+            maturity,
+        })
+    }
+
+    /// Number of distinct timer values a fish can have.
+    fn timers(&self) -> usize {
+        self.spawn_interval + self.maturity
+    }
+
+    /// Matrix taking the fish counted by timer one day ahead.
+    fn transition<T: Clone + Zero + One>(&self) -> Matrix<T> {
+        let mut m = Matrix::zero(self.timers());
+        for timer in 1..self.timers() {
+            m.set(timer - 1, timer, T::one());
+        }
+        // spawning fish restart their timer, and their offspring start at the longest timer
+        m.set(self.spawn_interval - 1, 0, T::one());
+        let newborn = m.get(self.timers() - 1, 0).clone() + T::one();
+        m.set(self.timers() - 1, 0, newborn);
+        m
+    }
+}
+
+/// Square matrix, stored row by row.
+#[derive(Debug, Clone, PartialEq)]
+struct Matrix<T> {
+    n: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone + Zero + One> Matrix<T> {
+    fn zero(n: usize) -> Self {
+        Self {
+            n,
+            cells: vec![T::zero(); n * n],
+        }
+    }
+
+    fn identity(n: usize) -> Self {
+        let mut m = Self::zero(n);
+        for i in 0..n {
+            m.set(i, i, T::one());
+        }
+        m
+    }
+
+    fn get(&self, row: usize, col: usize) -> &T {
+        &self.cells[row * self.n + col]
+    }
+
+    fn set(&mut self, row: usize, col: usize, value: T) {
+        self.cells[row * self.n + col] = value;
+    }
+
+    /// Raises the matrix to the power of `exp` by repeated squaring.
+    fn pow(&self, mut exp: usize) -> Self {
+        let mut result = Self::identity(self.n);
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    fn apply(&self, v: &[T]) -> Vec<T> {
+        (0..self.n)
+            .map(|row| {
+                v.iter().enumerate().fold(T::zero(), |sum, (col, x)| {
+                    sum + self.get(row, col).clone() * x.clone()
+                })
+            })
+            .collect()
     }
-    fish_by_spawn_delay
 }
 
-fn simulate(fish_by_spawn_delay: &mut [u64], days_to_simulate: usize) -> u64 {
+impl<T: Clone + Zero + One> Mul for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut m = Matrix::zero(self.n);
+        for row in 0..self.n {
+            for col in 0..self.n {
+                let value = (0..self.n).fold(T::zero(), |sum, i| {
+                    sum + self.get(row, i).clone() * rhs.get(i, col).clone()
+                });
+                m.set(row, col, value);
+            }
+        }
+        m
+    }
+}
+
+fn parse_input(input: &str, life_cycle: &LifeCycle) -> Result<Vec<u64>> {
+    let mut fish_by_spawn_delay = vec![0; life_cycle.timers()];
+    for timer in input.trim().split(',') {
+        let timer = timer
+            .trim()
+            .parse::<usize>()
+            .with_context(|| format!("unable to parse timer '{}'", timer))?;
+        if timer >= life_cycle.timers() {
+            bail!("timer {} exceeds the life cycle", timer);
+        }
+        fish_by_spawn_delay[timer] += 1;
+    }
+    Ok(fish_by_spawn_delay)
+}
+
+/// Steps the population one day at a time, returning the final number of fish.
+fn simulate<T>(fish_by_spawn_delay: &mut [T], life_cycle: &LifeCycle, days_to_simulate: usize) -> T
+where
+    T: Clone + Zero + Add<Output = T>,
+{
     for _ in 0..days_to_simulate {
         let to_be_spawned = fish_by_spawn_delay
             .iter_mut()
             .rev()
-            .fold(0, |a, b| std::mem::replace(b, a));
+            .fold(T::zero(), |a, b| std::mem::replace(b, a));
         // re-introduce spawning fish into the population
-        fish_by_spawn_delay[6] += to_be_spawned;
+        let restarted = fish_by_spawn_delay[life_cycle.spawn_interval - 1].clone();
+        fish_by_spawn_delay[life_cycle.spawn_interval - 1] = restarted + to_be_spawned.clone();
         // introduce their offspring
-        fish_by_spawn_delay[MAX_AGE - 1] = to_be_spawned;
+        let newborn = fish_by_spawn_delay[life_cycle.timers() - 1].clone();
+        fish_by_spawn_delay[life_cycle.timers() - 1] = newborn + to_be_spawned;
     }
-    fish_by_spawn_delay.iter().sum()
+    fish_by_spawn_delay
+        .iter()
+        .fold(T::zero(), |sum, n| sum + n.clone())
+}
+
+/// Jumps the population ahead using the transition matrix raised to the number of days, which
+/// takes `O(log days)` matrix multiplications.
+fn fast_forward<T>(fish_by_spawn_delay: &[T], life_cycle: &LifeCycle, days_to_simulate: usize) -> T
+where
+    T: Clone + Zero + One,
+{
+    life_cycle
+        .transition()
+        .pow(days_to_simulate)
+        .apply(fish_by_spawn_delay)
+        .into_iter()
+        .fold(T::zero(), |sum, n| sum + n)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn example_input() -> Vec<u64> {
+        parse_input("3,4,3,1,2", &LifeCycle::LANTERNFISH).unwrap()
+    }
+
     #[test]
     fn test_part1() {
-        let input = parse_input("3,4,3,1,2");
-        assert_eq!(5934, part1(input));
+        let input = example_input();
+        assert_eq!(
+            BigUint::from(5934u32),
+            part1(&input, &LifeCycle::LANTERNFISH)
+        );
     }
 
     #[test]
     fn test_part2() {
-        let input = parse_input("3,4,3,1,2");
-        assert_eq!(26984457539, part2(input));
+        let input = example_input();
+        assert_eq!(
+            BigUint::from(26984457539u64),
+            part2(&input, &LifeCycle::LANTERNFISH)
+        );
+    }
+
+    #[test]
+    fn test_fast_forward_matches_simulation() {
+        let life_cycles = [
+            LifeCycle::LANTERNFISH,
+            LifeCycle::new(3, 0).unwrap(),
+            LifeCycle::new(1, 4).unwrap(),
+        ];
+        for life_cycle in life_cycles {
+            let input = parse_input("0,1,2,2", &life_cycle).unwrap();
+            for days in [0, 1, 2, 17, 18, 80, 99] {
+                assert_eq!(
+                    simulate(&mut input.clone(), &life_cycle, days),
+                    fast_forward(&input, &life_cycle, days),
+                    "{:?} after {} days",
+                    life_cycle,
+                    days
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_horizon() {
+        let input = example_input()
+            .into_iter()
+            .map(BigUint::from)
+            .collect::<Vec<_>>();
+        let expected = simulate(&mut input.clone(), &LifeCycle::LANTERNFISH, 2000);
+        assert_eq!(
+            expected,
+            fast_forward(&input, &LifeCycle::LANTERNFISH, 2000)
+        );
+        assert!(expected > BigUint::from(u128::MAX));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_input("3,9", &LifeCycle::LANTERNFISH).is_err());
+        assert!(parse_input("3,x", &LifeCycle::LANTERNFISH).is_err());
+        assert_eq!(
+            vec![0, 1, 1, 0],
+            parse_input("1,2\n", &LifeCycle::new(2, 2).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_life_cycle() {
+        assert!(LifeCycle::new(0, 2).is_err());
+        assert_eq!(LifeCycle::LANTERNFISH, LifeCycle::new(7, 2).unwrap());
+        // short life cycles outgrow u64, in the result or in the powers of the transition matrix
+        for (spawn_interval, maturity) in [(2, 4), (3, 3), (1, 1)] {
+            let life_cycle = LifeCycle::new(spawn_interval, maturity).unwrap();
+            let input = parse_input("0,1,1", &life_cycle).unwrap();
+            assert_eq!(
+                simulate(&mut big_counts(&input), &life_cycle, 256),
+                part2(&input, &life_cycle)
+            );
+        }
+        let life_cycle = LifeCycle::new(2, 4).unwrap();
+        let input = parse_input("0,1,1", &life_cycle).unwrap();
+        assert!(part2(&input, &life_cycle) > BigUint::from(u64::MAX));
     }
 }
@@ -1,53 +1,106 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use itertools::Itertools;
-use std::{fs::read_to_string, path::Path};
+use std::{fs::read_to_string, ops::RangeInclusive, path::Path};
+
+use crate::{input, profile, Solver};
 
 pub fn solve_puzzle(input_path: &Path) -> Result<()> {
-    let input = parse_input(&read_to_string(input_path)?);
-    println!("Day7, Part1: {}", part1(&input));
-    println!("Day7, Part2: {}", part2(&input));
+    let input = parse_input(&read_to_string(input_path)?)?;
+    println!("Day7, Part1: {}", profile::part(7, 1, || part1(&input))?);
+    println!("Day7, Part2: {}", profile::part(7, 2, || part2(&input))?);
     Ok(())
 }
 
-fn part1(input: &[i32]) -> i32 {
-    minmax_range(input)
-        .map(|pos| {
-            input
-                .iter()
-                .fold(0, |fuel, crab_pos| fuel + (crab_pos - pos).abs())
-        })
-        .min()
-        .unwrap()
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(&parse_input(text)?)?.to_string()),
+    |text| Ok(part2(&parse_input(text)?)?.to_string()),
+];
+
+fn part1(input: &[i32]) -> Result<i32> {
+    let (_, fuel) = align_linear(input).context("no crabs")?;
+    Ok(fuel)
+}
+
+fn part2(input: &[i32]) -> Result<i32> {
+    let (_, fuel) = align_triangular(input).context("no crabs")?;
+    Ok(fuel)
 }
 
-fn part2(input: &[i32]) -> i32 {
-    fn triangular(n: i32) -> i32 {
-        n * (n + 1) / 2
+fn parse_input(input: &str) -> Result<Vec<i32>> {
+    input::comma_separated(input)
+}
+
+fn triangular(n: i32) -> i32 {
+    n * (n + 1) / 2
+}
+
+/// Total fuel for all crabs to move to `pos`, when moving a crab `n` steps costs `cost(n)`.
+fn fuel(crabs: &[i32], pos: i32, cost: impl Fn(i32) -> i32) -> i32 {
+    crabs.iter().map(|crab| cost((crab - pos).abs())).sum()
+}
+
+/// Cheapest `(position, fuel)` when every step costs 1.
+///
+/// Any median of the positions minimizes the summed distance.
+fn align_linear(crabs: &[i32]) -> Option<(i32, i32)> {
+    if crabs.is_empty() {
+        return None;
     }
+    let mut crabs = crabs.to_vec();
+    let mid = crabs.len() / 2;
+    let (_, &mut median, _) = crabs.select_nth_unstable(mid);
+    Some((median, fuel(&crabs, median, |n| n)))
+}
 
-    minmax_range(input)
-        .map(|pos| {
-            input.iter().fold(0, |fuel, crab_pos| {
-                fuel + triangular((crab_pos - pos).abs())
-            })
-        })
-        .min()
-        .unwrap()
+/// Cheapest `(position, fuel)` when moving `n` steps costs `1 + 2 + .. + n`.
+///
+/// The cost is `(n² + n) / 2`; the squared part alone is minimized by the mean, and the linear part
+/// can move the optimum at most half a step away from it, so only the positions around the mean
+/// are searched.
+fn align_triangular(crabs: &[i32]) -> Option<(i32, i32)> {
+    if crabs.is_empty() {
+        return None;
+    }
+    let sum = crabs.iter().map(|&c| c as i64).sum::<i64>();
+    let len = crabs.len() as i64;
+    let mean_floor = sum.div_euclid(len) as i32;
+    align_convex(crabs, triangular, Some(mean_floor - 1..=mean_floor + 1))
 }
 
-fn parse_input(input: &str) -> Vec<i32> {
-    input
-        .split(',')
-        .map(|s| s.parse::<i32>().expect("unable to parse number"))
-        .collect_vec()
+/// From the first to the last crab, empty without crabs.
+fn positions(crabs: &[i32]) -> RangeInclusive<i32> {
+    match crabs.iter().copied().minmax().into_option() {
+        Some((min, max)) => min..=max,
+        None => RangeInclusive::new(1, 0),
+    }
 }
 
-fn minmax_range(input: &[i32]) -> std::ops::RangeInclusive<i32> {
-    match input.iter().minmax() {
-        itertools::MinMaxResult::NoElements => 0..=0,
-        itertools::MinMaxResult::OneElement(p) => *p..=*p,
-        itertools::MinMaxResult::MinMax(a, b) => *a..=*b,
+/// Cheapest `(position, fuel)` within `within` for any convex, non-decreasing per-crab `cost` of
+/// moving `n` steps, searching the positions from the first to the last crab unless narrowed down.
+///
+/// The total fuel is then convex in the position as well, so the optimum is found by binary
+/// searching for the first position where moving one step further no longer lowers the fuel. The
+/// positions from the first to the last crab always hold the optimum.
+fn align_convex(
+    crabs: &[i32],
+    cost: impl Fn(i32) -> i32,
+    within: Option<RangeInclusive<i32>>,
+) -> Option<(i32, i32)> {
+    let within = within.unwrap_or_else(|| positions(crabs));
+    if crabs.is_empty() || within.is_empty() {
+        return None;
     }
+    let (mut lo, mut hi) = within.into_inner();
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if fuel(crabs, mid, &cost) <= fuel(crabs, mid + 1, &cost) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    Some((lo, fuel(crabs, lo, &cost)))
 }
 
 #[cfg(test)]
@@ -56,13 +109,66 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let input = parse_input("16,1,2,0,4,2,7,1,2,14");
-        assert_eq!(37, part1(&input));
+        let input = parse_input("16,1,2,0,4,2,7,1,2,14").unwrap();
+        assert_eq!(37, part1(&input).unwrap());
     }
 
     #[test]
     fn test_part2() {
-        let input = parse_input("16,1,2,0,4,2,7,1,2,14");
-        assert_eq!(168, part2(&input));
+        let input = parse_input("16,1,2,0,4,2,7,1,2,14").unwrap();
+        assert_eq!(168, part2(&input).unwrap());
+    }
+
+    fn brute_force(crabs: &[i32], cost: impl Fn(i32) -> i32) -> Option<i32> {
+        positions(crabs).map(|pos| fuel(crabs, pos, &cost)).min()
+    }
+
+    fn random_crabs(rng: &mut impl rand::Rng) -> Vec<i32> {
+        let len = rng.gen_range(1..50);
+        (0..len).map(|_| rng.gen_range(-20..200)).collect_vec()
+    }
+
+    #[test]
+    fn test_alignment_positions() {
+        let input = parse_input("16,1,2,0,4,2,7,1,2,14").unwrap();
+        assert_eq!(Some((2, 37)), align_linear(&input));
+        assert_eq!(Some((5, 168)), align_triangular(&input));
+        assert_eq!(Some((2, 37)), align_convex(&input, |n| n, None));
+        assert_eq!(Some((5, 168)), align_convex(&input, triangular, None));
+        assert_eq!(None, align_linear(&[]));
+        assert_eq!(None, align_triangular(&[]));
+        assert_eq!(None, align_convex(&[], |n| n, None));
+        assert_eq!(None, align_convex(&[], |n| n, Some(0..=10)));
+        assert_eq!(None, align_convex(&input, |n| n, Some(positions(&[]))));
+    }
+
+    #[test]
+    fn test_cross_check_brute_force() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..200 {
+            let crabs = random_crabs(&mut rng);
+            let expected_linear = brute_force(&crabs, |n| n);
+            let expected_triangular = brute_force(&crabs, triangular);
+            let expected_squared = brute_force(&crabs, |n| n * n);
+            assert_eq!(expected_linear, align_linear(&crabs).map(|(_, f)| f));
+            assert_eq!(
+                expected_linear,
+                align_convex(&crabs, |n| n, None).map(|(_, f)| f)
+            );
+            assert_eq!(
+                expected_triangular,
+                align_triangular(&crabs).map(|(_, f)| f)
+            );
+            assert_eq!(
+                expected_triangular,
+                align_convex(&crabs, triangular, None).map(|(_, f)| f)
+            );
+            assert_eq!(
+                expected_squared,
+                align_convex(&crabs, |n| n * n, None).map(|(_, f)| f)
+            );
+        }
     }
 }
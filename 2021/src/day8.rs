@@ -1,140 +1,72 @@
-use anyhow::Result;
-use itertools::Itertools;
-use std::{collections::HashSet, fs::read_to_string, path::Path};
+use anyhow::{Context, Result};
+use std::{fs::read_to_string, path::Path};
+
+use self::wiring::{segments, Display, Segments};
+use crate::{profile, Solver};
+
+mod wiring;
 
 pub fn solve_puzzle(input_path: &Path) -> Result<()> {
     let input = read_to_string(input_path)?;
-    let input = parse_input(&input);
-    println!("Day8, Part1: {}", part1(&input));
-    println!("Day8, Part2: {}", part2(&input));
+    let input = parse_input(&input)?;
+    println!(
+        "Day8, Part1: {}",
+        profile::part(8, 1, || Ok(part1(&input)))?
+    );
+    println!("Day8, Part2: {}", profile::part(8, 2, || part2(&input))?);
     Ok(())
 }
 
-fn part1(input: &[Vec<String>]) -> i32 {
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(&parse_input(text)?).to_string()),
+    |text| Ok(part2(&parse_input(text)?)?.to_string()),
+];
+
+struct Note {
+    patterns: Vec<Segments>,
+    output: Vec<Segments>,
+}
+
+fn part1(input: &[Note]) -> usize {
+    let display = Display::seven_segment_digits();
     input
         .iter()
-        .flat_map(|v| {
-            v[10..].iter().map(|s| match s.len() {
-                2 | 4 | 3 | 7 => 1,
-                _ => 0,
-            })
-        })
-        .sum()
+        .flat_map(|note| note.output.iter())
+        .filter(|&&o| display.identified_by_segment_count(o))
+        .count()
 }
 
-fn part2(input: &[Vec<String>]) -> usize {
+fn part2(input: &[Note]) -> Result<usize> {
+    let display = Display::seven_segment_digits();
     input
         .iter()
-        .map(|input| {
-            let (input, output) = input.split_at(10);
-            let mut input = input
-                .iter()
-                .map(|l| l.chars().collect::<HashSet<char>>())
-                .collect_vec();
-            let output = output
-                .iter()
-                .map(|l| l.chars().collect::<HashSet<char>>())
-                .collect_vec();
-
-            let one = input.remove(
-                input
-                    .iter()
-                    .find_position(|s| s.len() == 2)
-                    .map(|(p, _)| p)
-                    .unwrap(),
-            );
-            let four = input.remove(
-                input
-                    .iter()
-                    .find_position(|s| s.len() == 4)
-                    .map(|(p, _)| p)
-                    .unwrap(),
-            );
-            let seven = input.remove(
-                input
-                    .iter()
-                    .find_position(|s| s.len() == 3)
-                    .map(|(p, _)| p)
-                    .unwrap(),
-            );
-            let eight = input.remove(
-                input
-                    .iter()
-                    .find_position(|s| s.len() == 7)
-                    .map(|(p, _)| p)
-                    .unwrap(),
-            );
-
-            let six = input.remove(
-                input
-                    .iter()
-                    .find_position(|s| s.len() == 6 && s.difference(&one).count() == 5)
-                    .map(|(p, _)| p)
-                    .unwrap(),
-            );
-
-            let nine = input.remove(
-                input
-                    .iter()
-                    .find_position(|s| s.len() == 6 && s.difference(&four).count() == 2)
-                    .map(|(p, _)| p)
-                    .unwrap(),
-            );
-
-            let zero = input.remove(
-                input
-                    .iter()
-                    .find_position(|s| s.len() == 6)
-                    .map(|(p, _)| p)
-                    .unwrap(),
-            );
-
-            let three = input.remove(
-                input
-                    .iter()
-                    .find_position(|s| s.difference(&seven).count() == 2)
-                    .map(|(p, _)| p)
-                    .unwrap(),
-            );
-
-            let five = input.remove(
-                input
-                    .iter()
-                    .find_position(|s| s.difference(&four).count() == 2)
-                    .map(|(p, _)| p)
-                    .unwrap(),
-            );
-
-            let two = input.remove(0);
-
-            let ciphers = [zero, one, two, three, four, five, six, seven, eight, nine];
-
-            output
-                .into_iter()
-                .map(|o| {
-                    ciphers
-                        .iter()
-                        .position(|c| c.symmetric_difference(&o).count() == 0)
-                        .unwrap()
-                })
-                .zip([1000usize, 100, 10, 1])
-                .map(|(a, b)| a * b)
-                .sum::<usize>()
+        .enumerate()
+        .map(|(line, note)| {
+            let decoder = display
+                .solve(&note.patterns)
+                .with_context(|| format!("line {}", line + 1))?;
+            note.output.iter().try_fold(0, |value, &o| {
+                Ok(value * display.symbol_count() + decoder.decode(o)?)
+            })
         })
         .sum()
 }
 
-fn parse_input(input: &str) -> Vec<Vec<String>> {
-    let input = input
+fn parse_input(input: &str) -> Result<Vec<Note>> {
+    input
         .lines()
-        .map(|l| {
-            l.replace(" | ", " ")
-                .split(' ')
-                .map(|s| s.to_string())
-                .collect_vec()
+        .enumerate()
+        .map(|(line, l)| {
+            let parse = |s: &str| s.split_whitespace().map(segments).collect::<Result<_>>();
+            let (patterns, output) = l
+                .split_once(" | ")
+                .context("missing ' | ' separator")
+                .and_then(|(patterns, output)| Ok((parse(patterns)?, parse(output)?)))
+                .with_context(|| format!("line {}", line + 1))?;
+            Ok(Note { patterns, output })
         })
-        .collect_vec();
-    input
+        .collect()
 }
 
 #[cfg(test)]
@@ -157,13 +89,19 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let input = parse_input(EXAMPLE_INPUT);
+        let input = parse_input(EXAMPLE_INPUT).unwrap();
         assert_eq!(26, part1(&input));
     }
 
     #[test]
     fn test_part2() {
-        let input = parse_input(EXAMPLE_INPUT);
-        assert_eq!(61229, part2(&input));
+        let input = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(61229, part2(&input).unwrap());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_input("ab cf").is_err());
+        assert!(parse_input("ab cf | fx").is_err());
     }
 }
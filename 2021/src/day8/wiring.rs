@@ -0,0 +1,204 @@
+//! Recovery of scrambled segment display wirings.
+//!
+//! A display is defined by the segments lit for each of its symbols. When the wires to the
+//! segments get mixed up, the wiring can be recovered from the patterns seen for all the symbols,
+//! provided only one permutation of the wires explains those patterns. All permutations are
+//! evaluated up front, so solving a scrambled display afterwards is a single lookup.
+
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+use std::collections::{hash_map::Entry, HashMap};
+
+/// Set of segments, with segment `a` in bit 0, `b` in bit 1 and so on.
+pub(crate) type Segments = u8;
+
+const MAX_SEGMENTS: u32 = Segments::BITS;
+
+/// Parses a pattern such as `"acf"` into the set of segments it lights.
+pub(crate) fn segments(pattern: &str) -> Result<Segments> {
+    pattern.chars().try_fold(0, |segments, c| {
+        let segment = (c as u32).wrapping_sub('a' as u32);
+        if segment >= MAX_SEGMENTS {
+            bail!("invalid segment '{}' in pattern '{}'", c, pattern);
+        }
+        Ok(segments | 1 << segment)
+    })
+}
+
+/// Maps lit segments back to the value of the symbol they show.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Decoder(Vec<Option<usize>>);
+
+impl Decoder {
+    pub(crate) fn decode(&self, pattern: Segments) -> Result<usize> {
+        self.0
+            .get(pattern as usize)
+            .copied()
+            .flatten()
+            .ok_or_else(|| anyhow!("pattern {:#010b} is no symbol", pattern))
+    }
+}
+
+pub(crate) struct Display {
+    symbols: Vec<Segments>,
+    /// Decoders keyed by the sorted patterns of all symbols; `None` if the wiring is ambiguous.
+    wirings: HashMap<Vec<Segments>, Option<Decoder>>,
+}
+
+impl Display {
+    /// The digits 0-9 of a standard seven segment display.
+    pub(crate) fn seven_segment_digits() -> Self {
+        let digits = [
+            "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+        ];
+        Self::new(&digits.map(|d| segments(d).expect("valid digit definition")))
+            .expect("valid display definition")
+    }
+
+    /// Creates a display where `symbols[n]` holds the segments lit to show the value `n`.
+    pub(crate) fn new(symbols: &[Segments]) -> Result<Self> {
+        if let Some(duplicate) = symbols.iter().duplicates().next() {
+            bail!("multiple symbols use the pattern {:#010b}", duplicate);
+        }
+        let segment_count =
+            (MAX_SEGMENTS - symbols.iter().fold(0, |a, s| a | s).leading_zeros()) as usize;
+
+        let mut wirings = HashMap::new();
+        for wires in (0..segment_count).permutations(segment_count) {
+            let rewire = |segments: Segments| {
+                wires
+                    .iter()
+                    .enumerate()
+                    .filter(|(segment, _)| segments & 1 << segment != 0)
+                    .fold(0, |wired, (_, wire)| wired | 1 << wire)
+            };
+            let mut table = vec![None; 1 << segment_count];
+            for (value, &symbol) in symbols.iter().enumerate() {
+                table[rewire(symbol) as usize] = Some(value);
+            }
+            let decoder = Decoder(table);
+            let key = symbols.iter().map(|&s| rewire(s)).sorted().collect_vec();
+            match wirings.entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert(Some(decoder));
+                }
+                Entry::Occupied(mut entry) => {
+                    if entry.get().as_ref() != Some(&decoder) {
+                        entry.insert(None);
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            symbols: symbols.to_vec(),
+            wirings,
+        })
+    }
+
+    pub(crate) fn symbol_count(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Whether the number of lit segments alone tells which symbol `pattern` shows.
+    pub(crate) fn identified_by_segment_count(&self, pattern: Segments) -> bool {
+        self.symbols
+            .iter()
+            .filter(|s| s.count_ones() == pattern.count_ones())
+            .count()
+            == 1
+    }
+
+    /// Finds the decoder for a scrambled display, given the patterns seen for all its symbols.
+    pub(crate) fn solve(&self, patterns: &[Segments]) -> Result<&Decoder> {
+        let key = patterns.iter().copied().sorted().dedup().collect_vec();
+        match self.wirings.get(&key) {
+            Some(Some(decoder)) => Ok(decoder),
+            Some(None) => bail!("ambiguous wiring for patterns {:?}", patterns),
+            None => bail!("no wiring produces patterns {:?}", patterns),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(input: &str) -> Result<Vec<Segments>> {
+        input.split(' ').map(segments).collect()
+    }
+
+    #[test]
+    fn test_segments() -> Result<()> {
+        assert_eq!(0b101, segments("ac")?);
+        assert_eq!(0b1000_0000, segments("h")?);
+        assert!(segments("ai").is_err());
+        assert!(segments("A").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve() -> Result<()> {
+        let display = Display::seven_segment_digits();
+        let decoder = display.solve(&patterns(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab",
+        )?)?;
+        let output = patterns("cdfeb fcadb cdfeb cdbaf")?
+            .into_iter()
+            .map(|p| decoder.decode(p))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(vec![5, 3, 5, 3], output);
+        assert!(decoder.decode(segments("ae")?).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_impossible() -> Result<()> {
+        let display = Display::seven_segment_digits();
+        assert!(display.solve(&patterns("a b")?).is_err());
+        assert!(display
+            .solve(&patterns(
+                "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb abc",
+            )?)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_ambiguous() -> Result<()> {
+        // swapping the wires of 'a' and 'b' can't be detected
+        let display = Display::new(&patterns("a b ab abc")?)?;
+        assert!(display.solve(&patterns("b a ab abc")?).is_err());
+
+        // ...unless it shows up in a symbol
+        let display = Display::new(&patterns("a b ab abc ac")?)?;
+        let decoder = display.solve(&patterns("b a ab abc bc")?)?;
+        assert_eq!(0, decoder.decode(segments("b")?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_symbols() -> Result<()> {
+        assert!(Display::new(&patterns("ab ba")?).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_hex_digits() -> Result<()> {
+        const HEX: &str = "abcefg cf acdeg acdfg bcdf abdfg abdefg acf abcdefg abcdfg abcdef bdefg abeg cdefg abdeg abde";
+        let scramble = |p: &str| -> String {
+            p.chars()
+                .map(|c| "deafgbc".as_bytes()[(c as u8 - b'a') as usize] as char)
+                .collect()
+        };
+        let display = Display::new(&patterns(HEX)?)?;
+        assert_eq!(16, display.symbol_count());
+
+        let scrambled = HEX.split(' ').map(scramble).collect_vec();
+        let decoder = display.solve(&patterns(&scrambled.iter().rev().join(" "))?)?;
+        for (value, pattern) in scrambled.iter().enumerate() {
+            assert_eq!(value, decoder.decode(segments(pattern)?)?);
+        }
+        Ok(())
+    }
+}
@@ -1,15 +1,35 @@
 use anyhow::Result;
-use image::{GrayImage, ImageBuffer, Luma, Pixel};
+use image::{GrayImage, ImageBuffer, Luma, Pixel, Rgb};
 use itertools::Itertools;
 use std::{ops::DerefMut, path::Path};
 
+use crate::{profile, Solver};
+use crate::visualize::{categorical, Grid, Indexed, Visualizer};
+
 pub fn solve_puzzle(input_path: &Path) -> Result<()> {
     let input = std::fs::read_to_string(input_path)?;
-    println!("Day9, Part1: {}", part1(parse_input(&input)?));
-    println!("Day9, Part2: {}", part2(parse_input(&input)?));
+    println!(
+        "Day9, Part1: {}",
+        profile::part(9, 1, || Ok(part1(parse_input(&input)?)))?
+    );
+    println!(
+        "Day9, Part2: {}",
+        profile::part(9, 2, || {
+            part2(parse_input(&input)?, Visualizer::new("day9_part2")?)
+        })?
+    );
     Ok(())
 }
 
+/// The parts, solving the puzzle input as text.
+pub(crate) const PARTS: [Solver; 2] = [
+    |text| Ok(part1(parse_input(text)?).to_string()),
+    |text| Ok(part2(parse_input(text)?, Visualizer::new("day9_part2")?)?.to_string()),
+];
+
+/// Height of the locations not belonging to any basin.
+const THRESHOLD: u8 = 9;
+
 fn parse_input(input: &str) -> Result<GrayImage> {
     let height = input.lines().count() as u32;
     let container = input
@@ -40,60 +60,43 @@ fn part1(map: GrayImage) -> u32 {
     local_minima.map(|m| 1 + m as u32).sum()
 }
 
-fn part2(mut map: GrayImage) -> usize {
-    const THRESHOLD: u8 = 9;
+fn part2(mut map: GrayImage, mut viz: Visualizer) -> Result<usize> {
+    let palette = Indexed {
+        first: THRESHOLD + 1,
+        colors: categorical((u8::MAX - THRESHOLD) as usize, 0),
+        background: Rgb([64, 64, 64]),
+    };
+    let glyphs = |v: u8| char::from_digit(v as u32, 10).unwrap_or('~');
+
     let mut filler = Luma([THRESHOLD + 1]);
-    let mut sizes = vec![];
+    let mut basins = vec![];
     for y in 0..map.height() {
         for x in 0..map.width() {
             let pixels_filled = flood_fill(filler, |p| p.0[0] < THRESHOLD, (x, y), &mut map);
             if pixels_filled > 0 {
+                basins.push((pixels_filled, filler.0[0]));
                 filler = Luma([filler.0[0] + 1]);
+                viz.draw(&Grid::new(&map, &palette).glyphs(&glyphs))?;
             }
-            sizes.push(pixels_filled);
         }
     }
 
-    #[cfg(feature = "visualize")]
-    visualize(&map, filler);
-
-    sizes.sort_unstable();
-    sizes.into_iter().rev().take(3).product()
-}
-
-#[cfg(feature = "visualize")]
-fn visualize(map: &GrayImage, filler: Luma<u8>) {
-    const THRESHOLD: u8 = 9;
-
-    use image::{Rgb, RgbImage};
-    use palette::{FromColor, Hsv, RgbHue, Srgb};
-    use rand::Rng;
-
-    let mut rgb = RgbImage::new(map.width(), map.height());
-    let mut rng = rand::thread_rng();
-    let colors = { THRESHOLD..filler.0[0] }
-        .map(|_| rng.gen_range(0f32..359f32))
-        .map(|hue| Hsv::new(RgbHue::from_degrees(hue), 1., 1.))
-        .map(Srgb::from_color)
-        .map(|srgb| {
-            let (r, g, b) = srgb.into_components();
-            Rgb([(r * 255.) as u8, (g * 255.) as u8, (b * 255.) as u8])
-        })
-        .collect_vec();
-    for y in 0..map.height() {
-        for x in 0..map.width() {
-            let g = map.get_pixel(x, y).0[0];
-            if g > THRESHOLD {
-                rgb.put_pixel(x, y, colors[(g - THRESHOLD - 1) as usize]);
-            }
-        }
-    }
-    let mut pb = std::path::PathBuf::from("assets_day9");
-    if !pb.exists() {
-        std::fs::create_dir(&pb).unwrap();
+    basins.sort_unstable();
+    let largest = basins.into_iter().rev().take(3).collect_vec();
+    if viz.is_enabled() {
+        let cells = map
+            .enumerate_pixels()
+            .filter(|(_, _, p)| largest.iter().any(|&(_, basin)| basin == p.0[0]))
+            .map(|(x, y, _)| (x, y))
+            .collect_vec();
+        let grid = Grid::new(&map, &palette)
+            .glyphs(&glyphs)
+            .highlight(&cells, Rgb([255, 255, 255]));
+        viz.draw(&grid)?;
     }
-    pb.push("day9_part2_viz.png");
-    rgb.save(&pb).unwrap();
+    viz.finish()?;
+
+    Ok(largest.into_iter().map(|(size, _)| size).product())
 }
 
 fn flood_fill<F, P, Container>(
@@ -134,6 +137,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::visualize::golden;
     use anyhow::Result;
 
     const INPUT: &str = indoc::indoc! {"
@@ -155,8 +159,17 @@ mod tests {
     #[test]
     fn example_part2() -> Result<()> {
         let input = parse_input(INPUT);
-        let result = part2(input?);
+        let result = part2(input?, golden::disabled())?;
         assert_eq!(1134, result);
         Ok(())
     }
+
+    #[test]
+    fn golden_part2() -> Result<()> {
+        let (viz, frames) = golden::recorder();
+        part2(parse_input(INPUT)?, viz)?;
+        let frames = frames.borrow();
+        assert_eq!(5, frames.len());
+        golden::assert_golden("day9_part2", frames.last().unwrap())
+    }
 }
@@ -0,0 +1,181 @@
+//! Syntax checking of nested delimiters, such as brackets.
+//!
+//! Characters that are neither opening nor closing delimiters are skipped, so the checker can be
+//! used on structures with content between the delimiters as well.
+
+use anyhow::{bail, Result};
+use itertools::Itertools;
+
+/// Outcome of checking a single line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Syntax {
+    /// Every opened delimiter is closed again.
+    Complete,
+    /// Some delimiters are left open; `completion` holds the closing delimiters needed, innermost
+    /// first.
+    Incomplete { completion: String },
+    /// The closing delimiter at `position` (in chars) doesn't match. `expected` is the closing
+    /// delimiter for the innermost open delimiter, if any.
+    Corrupted {
+        position: usize,
+        found: char,
+        expected: Option<char>,
+    },
+}
+
+pub(crate) struct DelimiterChecker {
+    pairs: Vec<(char, char)>,
+}
+
+impl DelimiterChecker {
+    /// Creates a checker for the given `(open, close)` pairs.
+    pub(crate) fn new(pairs: &[(char, char)]) -> Result<Self> {
+        if let Some(c) = pairs.iter().flat_map(|&(o, c)| [o, c]).duplicates().next() {
+            bail!("'{}' is used by more than one delimiter", c);
+        }
+        Ok(Self {
+            pairs: pairs.to_vec(),
+        })
+    }
+
+    pub(crate) fn check(&self, line: &str) -> Syntax {
+        let mut expected_closing = vec![];
+        for (position, c) in line.chars().enumerate() {
+            if let Some(&(_, close)) = self.pairs.iter().find(|(open, _)| *open == c) {
+                expected_closing.push(close);
+            } else if self.pairs.iter().any(|&(_, close)| close == c) {
+                let expected = expected_closing.pop();
+                if expected != Some(c) {
+                    return Syntax::Corrupted {
+                        position,
+                        found: c,
+                        expected,
+                    };
+                }
+            }
+        }
+
+        if expected_closing.is_empty() {
+            Syntax::Complete
+        } else {
+            Syntax::Incomplete {
+                completion: expected_closing.into_iter().rev().collect(),
+            }
+        }
+    }
+}
+
+/// Assigns a score to the outcome of a syntax check, or `None` if the outcome isn't scored.
+pub(crate) trait Scoring {
+    fn score(&self, syntax: &Syntax) -> Option<usize>;
+}
+
+impl<F: Fn(&Syntax) -> Option<usize>> Scoring for F {
+    fn score(&self, syntax: &Syntax) -> Option<usize> {
+        self(syntax)
+    }
+}
+
+/// Scores corrupted lines by the first illegal delimiter found.
+pub(crate) struct CorruptionScoring<'a>(pub(crate) &'a [(char, usize)]);
+
+impl Scoring for CorruptionScoring<'_> {
+    fn score(&self, syntax: &Syntax) -> Option<usize> {
+        match syntax {
+            Syntax::Corrupted { found, .. } => points(self.0, *found),
+            _ => None,
+        }
+    }
+}
+
+/// Scores incomplete lines by their completion; for every delimiter in the completion, the score
+/// so far is multiplied by `multiplier` before adding the points for the delimiter.
+pub(crate) struct CompletionScoring<'a> {
+    pub(crate) multiplier: usize,
+    pub(crate) points: &'a [(char, usize)],
+}
+
+impl Scoring for CompletionScoring<'_> {
+    fn score(&self, syntax: &Syntax) -> Option<usize> {
+        match syntax {
+            Syntax::Incomplete { completion } => completion.chars().try_fold(0, |score, c| {
+                Some(score * self.multiplier + points(self.points, c)?)
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn points(table: &[(char, usize)], c: char) -> Option<usize> {
+    table.iter().find(|(t, _)| *t == c).map(|(_, p)| *p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() -> Result<()> {
+        let checker = DelimiterChecker::new(&[('(', ')'), ('[', ']')])?;
+        assert_eq!(Syntax::Complete, checker.check("([]())"));
+        assert_eq!(Syntax::Complete, checker.check("[1,[2,3]]"));
+        assert_eq!(
+            Syntax::Incomplete {
+                completion: "])".to_owned()
+            },
+            checker.check("([()")
+        );
+        assert_eq!(
+            Syntax::Corrupted {
+                position: 2,
+                found: ')',
+                expected: Some(']')
+            },
+            checker.check("([)]")
+        );
+        assert_eq!(
+            Syntax::Corrupted {
+                position: 2,
+                found: ']',
+                expected: None
+            },
+            checker.check("()]")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_pairs() -> Result<()> {
+        let checker = DelimiterChecker::new(&[('/', '\\'), ('a', 'z')])?;
+        assert_eq!(Syntax::Complete, checker.check("/a/\\z\\"));
+        assert!(DelimiterChecker::new(&[('|', '|')]).is_err());
+        assert!(DelimiterChecker::new(&[('(', ')'), ('[', ')')]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_scoring() {
+        let completion = CompletionScoring {
+            multiplier: 10,
+            points: &[(')', 1), (']', 2)],
+        };
+        let incomplete = Syntax::Incomplete {
+            completion: "])".to_owned(),
+        };
+        assert_eq!(Some(21), completion.score(&incomplete));
+        assert_eq!(None, completion.score(&Syntax::Complete));
+        assert_eq!(None, CorruptionScoring(&[(')', 3)]).score(&incomplete));
+
+        let by_position = |syntax: &Syntax| match syntax {
+            Syntax::Corrupted { position, .. } => Some(*position),
+            _ => None,
+        };
+        let corrupted = Syntax::Corrupted {
+            position: 7,
+            found: ')',
+            expected: None,
+        };
+        assert_eq!(Some(7), by_position.score(&corrupted));
+        assert_eq!(Some(3), CorruptionScoring(&[(')', 3)]).score(&corrupted));
+    }
+}
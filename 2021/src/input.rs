@@ -0,0 +1,162 @@
+//! Shared parsing of puzzle inputs.
+//!
+//! Values are decoded through [`FromStr`], and errors are reported with the line they occurred
+//! on, counting from 1.
+
+use anyhow::{Context, Result};
+use std::str::FromStr;
+
+/// Non-blank lines of the input with their line numbers, trimmed of surrounding whitespace.
+pub(crate) fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(n, l)| (n + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty())
+}
+
+/// Decodes every non-blank line as a single value.
+pub(crate) fn lines<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    numbered_lines(input)
+        .map(|(n, l)| decode(l).with_context(|| format!("line {}", n)))
+        .collect()
+}
+
+/// Decodes every whitespace separated word of the input.
+pub(crate) fn whitespace_separated<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    separated(input, |l| l.split_whitespace().collect())
+}
+
+/// Decodes every comma separated value of the input; whitespace around the values is ignored.
+pub(crate) fn comma_separated<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    separated(input, |l| l.split(',').map(str::trim).collect())
+}
+
+/// Decodes every block of lines separated by blank lines as a single value. The text given to
+/// [`FromStr`] holds the lines of the block, joined by `'\n'`. Errors name the line the block
+/// starts at, the first line of `input` being line `first_line` of the file it's read from.
+pub(crate) fn blocks<T>(input: &str, first_line: usize) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    let mut blocks = vec![];
+    let mut block: Option<(usize, String)> = None;
+    for (n, line) in input.lines().enumerate() {
+        let line = line.trim();
+        match (&mut block, line.is_empty()) {
+            (None, true) => {}
+            (None, false) => block = Some((first_line + n, line.to_owned())),
+            (Some((_, text)), false) => {
+                text.push('\n');
+                text.push_str(line);
+            }
+            (Some(_), true) => blocks.extend(block.take()),
+        }
+    }
+    blocks.extend(block);
+
+    blocks
+        .into_iter()
+        .map(|(n, text)| decode(&text).with_context(|| format!("block at line {}", n)))
+        .collect()
+}
+
+fn separated<T>(input: &str, split: fn(&str) -> Vec<&str>) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    let mut values = vec![];
+    for (n, line) in numbered_lines(input) {
+        for value in split(line) {
+            values.push(decode(value).with_context(|| format!("line {}", n))?);
+        }
+    }
+    Ok(values)
+}
+
+fn decode<T>(s: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    s.parse::<T>()
+        .map_err(Into::into)
+        .with_context(|| format!("unable to decode '{}'", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_lines() -> Result<()> {
+        assert_eq!(vec![1, 2, 3], lines::<u32>("1\n 2 \n\n3\n")?);
+        let err = lines::<u32>("1\n\nx\n").unwrap_err();
+        assert_eq!("line 3", err.to_string());
+        assert_eq!(
+            "unable to decode 'x'",
+            err.chain().nth(1).unwrap().to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_separated() -> Result<()> {
+        assert_eq!(vec![1, 2, 3, 4], whitespace_separated::<u8>("1  2\n3\t4")?);
+        assert_eq!(vec![1, 2, 3], comma_separated::<u8>("1, 2,3\n")?);
+        let err = comma_separated::<u8>("1,2\n3,-4").unwrap_err();
+        assert_eq!("line 2", err.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocks() -> Result<()> {
+        #[derive(Debug)]
+        struct Sum(u32);
+        impl FromStr for Sum {
+            type Err = anyhow::Error;
+            fn from_str(s: &str) -> Result<Self> {
+                Ok(Sum(lines::<u32>(s)?.into_iter().sum()))
+            }
+        }
+
+        let input = indoc! {"
+
+            1
+            2
+
+            3
+
+
+            4
+            5
+        "};
+        assert_eq!(
+            vec![3, 3, 9],
+            blocks::<Sum>(input, 1)?
+                .into_iter()
+                .map(|s| s.0)
+                .collect::<Vec<_>>()
+        );
+        let err = blocks::<Sum>("1\n\n2\nx", 1).unwrap_err();
+        assert_eq!("block at line 3", err.to_string());
+        let err = blocks::<Sum>("1\n\n2\nx", 3).unwrap_err();
+        assert_eq!("block at line 5", err.to_string());
+        Ok(())
+    }
+}
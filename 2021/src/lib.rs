@@ -0,0 +1,177 @@
+//! Solutions to the Advent of Code 2021 puzzles.
+//!
+//! Every day lives in its own module, with a `solve_puzzle` printing its answers for the binary,
+//! and is registered in [`PUZZLES`] so other tools can solve it from its puzzle input.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+mod delimiters;
+mod input;
+pub mod profile;
+pub mod visualize;
+
+/// Solves one part of a puzzle from the puzzle input, returning the answer.
+pub type Solver = fn(&str) -> Result<String>;
+
+/// A registered puzzle.
+pub struct Puzzle {
+    pub day: u32,
+    /// File name of the puzzle input in `assets`.
+    pub input: &'static str,
+    pub parts: [Solver; 2],
+}
+
+impl Puzzle {
+    /// Solves `part`, counting from 1.
+    pub fn solve(&self, part: usize, input: &str) -> Result<String> {
+        let Some(solver) = part.checked_sub(1).and_then(|i| self.parts.get(i)) else {
+            bail!("day {} has no part {}", self.day, part);
+        };
+        solver(input).with_context(|| format!("day {}, part {}", self.day, part))
+    }
+
+    /// The default location of the puzzle input, in `assets` in the crate directory.
+    pub fn input_path(&self) -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "assets", self.input]
+            .iter()
+            .collect()
+    }
+}
+
+/// The solved puzzles, ordered by day.
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle {
+        day: 1,
+        input: "day1.csv",
+        parts: day1::PARTS,
+    },
+    Puzzle {
+        day: 2,
+        input: "day2.csv",
+        parts: day2::PARTS,
+    },
+    Puzzle {
+        day: 3,
+        input: "day3.csv",
+        parts: day3::PARTS,
+    },
+    Puzzle {
+        day: 4,
+        input: "day4.txt",
+        parts: day4::PARTS,
+    },
+    Puzzle {
+        day: 5,
+        input: "day5.txt",
+        parts: day5::PARTS,
+    },
+    Puzzle {
+        day: 6,
+        input: "day6.txt",
+        parts: day6::PARTS,
+    },
+    Puzzle {
+        day: 7,
+        input: "day7.txt",
+        parts: day7::PARTS,
+    },
+    Puzzle {
+        day: 8,
+        input: "day8.txt",
+        parts: day8::PARTS,
+    },
+    Puzzle {
+        day: 9,
+        input: "day9.txt",
+        parts: day9::PARTS,
+    },
+    Puzzle {
+        day: 10,
+        input: "day10.txt",
+        parts: day10::PARTS,
+    },
+    Puzzle {
+        day: 11,
+        input: "day11.txt",
+        parts: day11::PARTS,
+    },
+    Puzzle {
+        day: 12,
+        input: "day12.txt",
+        parts: day12::PARTS,
+    },
+    Puzzle {
+        day: 13,
+        input: "day13.txt",
+        parts: day13::PARTS,
+    },
+    Puzzle {
+        day: 14,
+        input: "day14.txt",
+        parts: day14::PARTS,
+    },
+    Puzzle {
+        day: 15,
+        input: "day15.txt",
+        parts: day15::PARTS,
+    },
+    Puzzle {
+        day: 16,
+        input: "day16.txt",
+        parts: day16::PARTS,
+    },
+    Puzzle {
+        day: 17,
+        input: "day17.txt",
+        parts: day17::PARTS,
+    },
+];
+
+/// The registered puzzle of `day`.
+pub fn puzzle(day: u32) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|p| p.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_puzzles() -> Result<()> {
+        assert!(PUZZLES.windows(2).all(|w| w[0].day < w[1].day));
+        assert!(PUZZLES.iter().all(|p| p.input_path().exists()));
+        let day1 = puzzle(1).unwrap();
+        assert_eq!(
+            "7",
+            day1.solve(1, "199\n200\n208\n210\n200\n207\n240\n269\n260\n263")?
+        );
+        assert_eq!(
+            "5",
+            day1.solve(2, "199\n200\n208\n210\n200\n207\n240\n269\n260\n263")?
+        );
+        let err = day1.solve(1, "x").unwrap_err();
+        assert_eq!("day 1, part 1", err.to_string());
+        assert!(day1.solve(3, "").is_err());
+        assert!(puzzle(25).is_none());
+        Ok(())
+    }
+}
@@ -1,39 +1,49 @@
-use anyhow::{Context, Result};
-use csv::StringRecord;
-#[cfg(feature = "profile")]
-use dhat::{Dhat, DhatAlloc};
-use serde::Deserialize;
+use advent_of_code_2021::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day2, day3, day4, day5, day6,
+    day7, day8, day9, profile,
+    visualize::{self, Animation, Looping, Scaling, TerminalConfig},
+};
+use anyhow::{bail, Result};
 use std::path::PathBuf;
-use std::{fs::File, path::Path};
 use structopt::StructOpt;
 
-#[cfg(feature = "profile")]
-#[global_allocator]
-static ALLOCATOR: DhatAlloc = DhatAlloc;
-
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+#[cfg(feature = "dhat-heap")]
+aoc_profiling::global_allocator!();
+#[cfg(not(feature = "dhat-heap"))]
+aoc_profiling::counting_allocator!();
 
 #[derive(Debug, StructOpt)]
 struct Args {
     #[structopt(subcommand)]
     puzzle: Day,
     input: Option<PathBuf>,
+    /// Directory receiving the visualizations
+    #[structopt(long, default_value = "visualizations")]
+    visualize_dir: PathBuf,
+    /// Where visualizations are written: png, frames, gif and/or terminal
+    #[structopt(long, use_delimiter = true, default_value = "png")]
+    visualize_sinks: Vec<visualize::SinkKind>,
+    /// Scale visualizations by this factor, instead of fitting them within 256 pixels
+    #[structopt(long)]
+    visualize_scale: Option<u32>,
+    /// Delay between the frames of animations, in milliseconds
+    #[structopt(long, default_value = "100")]
+    visualize_delay: u32,
+    /// Play animations this many times, instead of looping forever
+    #[structopt(long)]
+    visualize_plays: Option<u16>,
+    /// Colours of the terminal: truecolor or 256
+    #[structopt(long, default_value = "truecolor")]
+    visualize_colors: visualize::ColorMode,
+    /// Wait for enter after every frame drawn on the terminal
+    #[structopt(long)]
+    visualize_step: bool,
+    /// Append the terminal output to this file instead of stdout
+    #[structopt(long)]
+    visualize_terminal_file: Option<PathBuf>,
+    /// Report the wall time, peak heap and number of allocations of every part
+    #[structopt(long)]
+    stats: bool,
 }
 
 #[derive(Debug, StructOpt)]
@@ -41,9 +51,20 @@ enum Day {
     Day1,
     Day2,
     Day3,
-    Day4,
+    Day4 {
+        /// Rules making a board win: rows, columns, diagonals, corners and/or full-card
+        #[structopt(long, use_delimiter = true, default_value = "rows,columns")]
+        rules: Vec<day4::WinRuleKind>,
+    },
     Day5,
-    Day6,
+    Day6 {
+        /// Days between the spawns of an adult fish
+        #[structopt(long, default_value = "7")]
+        spawn_interval: usize,
+        /// Extra days before a newborn fish spawns for the first time
+        #[structopt(long, default_value = "2")]
+        maturity: usize,
+    },
     Day7,
     Day8,
     Day9,
@@ -66,17 +87,42 @@ enum Day {
 }
 
 fn main() -> Result<()> {
-    #[cfg(feature = "profile")]
-    let _dhat = Dhat::start_heap_profiling();
-
     let args = Args::from_args_safe()?;
+    if args.stats {
+        if cfg!(feature = "dhat-heap") {
+            bail!("--stats counts allocations with an allocator of its own, drop dhat-heap");
+        }
+        aoc_profiling::stats::CountingAlloc::enable();
+    }
+    visualize::configure(visualize::Config {
+        dir: args.visualize_dir,
+        sinks: args.visualize_sinks,
+        scaling: args
+            .visualize_scale
+            .map_or(visualize::Config::default().scaling, Scaling::Factor),
+        animation: Animation {
+            delay: args.visualize_delay,
+            looping: args
+                .visualize_plays
+                .map_or(Looping::Forever, Looping::Times),
+        },
+        terminal: TerminalConfig {
+            colors: args.visualize_colors,
+            step: args.visualize_step,
+            file: args.visualize_terminal_file,
+        },
+    });
+    let stats = args.stats;
     match args.puzzle {
         Day::Day1 => day1::solve_puzzle(args.input.unwrap().as_path()),
         Day::Day2 => day2::solve_puzzle(args.input.unwrap().as_path()),
         Day::Day3 => day3::solve_puzzle(args.input.unwrap().as_path()),
-        Day::Day4 => day4::solve_puzzle(args.input.unwrap().as_path()),
+        Day::Day4 { rules } => day4::solve_puzzle(args.input.unwrap().as_path(), &rules),
         Day::Day5 => day5::solve_puzzle(args.input.unwrap().as_path()),
-        Day::Day6 => day6::solve_puzzle(args.input.unwrap().as_path()),
+        Day::Day6 {
+            spawn_interval,
+            maturity,
+        } => day6::solve_puzzle(args.input.unwrap().as_path(), spawn_interval, maturity),
         Day::Day7 => day7::solve_puzzle(args.input.unwrap().as_path()),
         Day::Day8 => day8::solve_puzzle(args.input.unwrap().as_path()),
         Day::Day9 => day9::solve_puzzle(args.input.unwrap().as_path()),
@@ -96,60 +142,9 @@ fn main() -> Result<()> {
         Day::Day23 => todo!(),
         Day::Day24 => todo!(),
         Day::Day25 => todo!(),
+    }?;
+    if stats {
+        print!("\n{}", profile::report());
     }
-}
-
-fn read_csv<T>(file_path: &Path, headers: &[&str], delimiter: u8) -> Result<Vec<T>>
-where
-    for<'de> T: Deserialize<'de>,
-{
-    let mut result = vec![];
-    let reader = File::open(file_path)?;
-    let mut rdr = csv::ReaderBuilder::default()
-        .delimiter(delimiter)
-        .trim(csv::Trim::All)
-        .from_reader(reader);
-    rdr.set_headers(StringRecord::from(headers));
-
-    for (line, record) in rdr.deserialize().enumerate() {
-        let record: T = record.context(line)?;
-        result.push(record);
-    }
-    Ok(result)
-}
-
-#[cfg(test)]
-fn create_input(dir: &tempfile::TempDir, lines: &[&str]) -> Result<PathBuf> {
-    let mut pb = PathBuf::from(dir.path());
-    pb.push("csvfile.csv");
-    let path = pb.as_path();
-    let mut file = File::create(path)?;
-    for l in lines {
-        use std::io::Write;
-        writeln!(file, "{}", l)?;
-    }
-    drop(file);
-    Ok(pb)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use anyhow::Result;
-
-    #[derive(Debug, Deserialize, PartialEq)]
-    struct TestInput {
-        val: f64,
-    }
-
-    #[test]
-    fn can_read_input() -> Result<()> {
-        let dir = tempfile::tempdir()?;
-        let path = create_input(&dir, &["123", "456"])?;
-        assert_eq!(
-            vec![TestInput { val: 123.0_f64 }, TestInput { val: 456.0_f64 },],
-            read_csv(path.as_path(), &["val"], b'\t')?
-        );
-        Ok(())
-    }
+    Ok(())
 }
@@ -0,0 +1,30 @@
+//! Profiling of the parts: of the heap with dhat when built with the `dhat-heap` feature, and of
+//! the time and allocations when run with `--stats`.
+
+use std::sync::Mutex;
+
+use anyhow::Result;
+use aoc_profiling::stats::{self, PartUsage};
+
+/// The parts solved so far.
+static USAGES: Mutex<Vec<PartUsage>> = Mutex::new(Vec::new());
+
+/// Solves `part` of `day`, measuring its time and allocations. Built with the `dhat-heap` feature,
+/// the heap is profiled meanwhile and the profile written to `dhat-heap-day<day>-part<part>.json`.
+pub fn part<T>(day: u32, part: u32, solve: impl FnOnce() -> Result<T>) -> Result<T> {
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = aoc_profiling::profile(&format!("day{}-part{}", day, part));
+    let (answer, usage) = stats::measure(solve);
+    USAGES.lock().unwrap().push(PartUsage {
+        year: 2021,
+        day,
+        part: part as usize,
+        usage,
+    });
+    answer
+}
+
+/// A table of the time and allocations of the parts solved so far.
+pub fn report() -> String {
+    stats::report(&USAGES.lock().unwrap())
+}
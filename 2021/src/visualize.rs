@@ -0,0 +1,354 @@
+//! Visualization of puzzle states as images.
+//!
+//! A solver creates a [`Visualizer`] and hands it [`Grid`]s; the visualizer draws them as frames,
+//! scales those up and passes them on to the configured [`FrameSink`]s. Without the `visualize`
+//! feature, visualizers have no sinks and nothing is written anywhere, so solvers can call them
+//! unconditionally. Use [`Visualizer::is_enabled`] to skip rendering frames nobody will see.
+
+use anyhow::{bail, Context, Result};
+use image::{imageops::FilterType, GrayImage, Rgb, RgbImage};
+use std::{borrow::Cow, fs::OpenOptions, path::PathBuf, str::FromStr, sync::OnceLock};
+
+pub(crate) use self::palette::{categorical, Grayscale, Indexed, Palette, Ramp};
+pub(crate) use self::sink::{Apng, FrameSink, Gif, PngSequence};
+pub use self::terminal::ColorMode;
+pub(crate) use self::terminal::Terminal;
+
+pub(crate) mod export;
+#[cfg(test)]
+pub(crate) mod golden;
+mod palette;
+mod sink;
+mod terminal;
+
+/// The kinds of sinks frames can be written to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SinkKind {
+    /// A single PNG file, animated if there's more than one frame.
+    Png,
+    /// Every frame as a PNG file of its own.
+    Frames,
+    /// All frames as a single animated GIF.
+    Gif,
+    /// ANSI escape sequences on stdout, or in the file of [`TerminalConfig`].
+    Terminal,
+}
+
+impl FromStr for SinkKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "png" => Ok(SinkKind::Png),
+            "frames" => Ok(SinkKind::Frames),
+            "gif" => Ok(SinkKind::Gif),
+            "terminal" => Ok(SinkKind::Terminal),
+            _ => bail!(
+                "unknown sink '{}', expected png, frames, gif or terminal",
+                s
+            ),
+        }
+    }
+}
+
+/// How frames are scaled before they are written. Scaling always uses whole factors, so every
+/// cell of a grid keeps the same size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scaling {
+    Factor(u32),
+    /// The largest factor keeping both sides within the given size, and at least 1.
+    Fit(u32),
+}
+
+impl Scaling {
+    fn factor(&self, width: u32, height: u32) -> u32 {
+        match *self {
+            Scaling::Factor(factor) => factor.max(1),
+            Scaling::Fit(size) => (size / width.max(height).max(1)).max(1),
+        }
+    }
+}
+
+/// How often an animation is played.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Looping {
+    Forever,
+    /// Played the given number of times in total, and at least once.
+    Times(u16),
+}
+
+/// Timing of the animated sinks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Animation {
+    /// Delay between frames, in milliseconds.
+    pub delay: u32,
+    pub looping: Looping,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminalConfig {
+    pub colors: ColorMode,
+    /// Wait for enter after every frame.
+    pub step: bool,
+    /// Append the escape sequences to this file instead of writing them to stdout.
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// Directory receiving the files of the sinks.
+    pub dir: PathBuf,
+    pub sinks: Vec<SinkKind>,
+    pub scaling: Scaling,
+    pub animation: Animation,
+    pub terminal: TerminalConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("visualizations"),
+            sinks: vec![SinkKind::Png],
+            scaling: Scaling::Fit(256),
+            animation: Animation {
+                delay: 100,
+                looping: Looping::Forever,
+            },
+            terminal: TerminalConfig {
+                colors: ColorMode::TrueColor,
+                step: false,
+                file: None,
+            },
+        }
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Sets the configuration used by [`Visualizer::new`]. Only the first configuration counts.
+pub fn configure(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+fn config() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+/// A grid of values to visualize, along with how its cells are drawn.
+pub(crate) struct Grid<'a> {
+    values: &'a GrayImage,
+    palette: &'a dyn Palette,
+    glyphs: Option<&'a dyn Fn(u8) -> char>,
+    highlights: &'a [(u32, u32)],
+    highlight: Rgb<u8>,
+}
+
+impl<'a> Grid<'a> {
+    pub fn new(values: &'a GrayImage, palette: &'a dyn Palette) -> Self {
+        Self {
+            values,
+            palette,
+            glyphs: None,
+            highlights: &[],
+            highlight: Rgb([255, 255, 255]),
+        }
+    }
+
+    /// Draws the cells as characters, on sinks showing text.
+    pub fn glyphs(mut self, glyphs: &'a dyn Fn(u8) -> char) -> Self {
+        self.glyphs = Some(glyphs);
+        self
+    }
+
+    /// Draws `cells` in `color`, whatever their value.
+    pub fn highlight(mut self, cells: &'a [(u32, u32)], color: Rgb<u8>) -> Self {
+        self.highlights = cells;
+        self.highlight = color;
+        self
+    }
+
+    pub fn value(&self, x: u32, y: u32) -> u8 {
+        self.values.get_pixel(x, y).0[0]
+    }
+
+    pub fn glyph_fn(&self) -> Option<&'a dyn Fn(u8) -> char> {
+        self.glyphs
+    }
+
+    pub fn is_highlighted(&self, x: u32, y: u32) -> bool {
+        self.highlights.contains(&(x, y))
+    }
+
+    /// The grid as an image of a pixel per cell.
+    pub fn colorize(&self) -> RgbImage {
+        let mut img = self.palette.colorize(self.values);
+        for &(x, y) in self.highlights {
+            if x < img.width() && y < img.height() {
+                img.put_pixel(x, y, self.highlight);
+            }
+        }
+        img
+    }
+}
+
+pub(crate) struct Visualizer {
+    sinks: Vec<Box<dyn FrameSink>>,
+    scaling: Scaling,
+}
+
+impl Visualizer {
+    /// A visualizer for the frames called `name`, using the sinks of the configuration.
+    pub fn new(name: &str) -> Result<Self> {
+        let config = config();
+        if !cfg!(feature = "visualize") {
+            return Ok(Self::with_sinks(vec![], config.scaling));
+        }
+        let sinks = config
+            .sinks
+            .iter()
+            .map(|kind| -> Result<Box<dyn FrameSink>> {
+                Ok(match kind {
+                    SinkKind::Png => Box::new(Apng::new(
+                        config.dir.join(format!("{}.png", name)),
+                        config.animation,
+                    )),
+                    SinkKind::Frames => Box::new(PngSequence::new(&config.dir, name)),
+                    SinkKind::Gif => Box::new(Gif::new(
+                        config.dir.join(format!("{}.gif", name)),
+                        config.animation,
+                    )),
+                    SinkKind::Terminal => terminal(&config.terminal)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self::with_sinks(sinks, config.scaling))
+    }
+
+    pub fn with_sinks(sinks: Vec<Box<dyn FrameSink>>, scaling: Scaling) -> Self {
+        Self { sinks, scaling }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.sinks.is_empty()
+    }
+
+    fn scale<'f>(&self, frame: &'f RgbImage) -> Cow<'f, RgbImage> {
+        match self.scaling.factor(frame.width(), frame.height()) {
+            1 => Cow::Borrowed(frame),
+            factor => Cow::Owned(image::imageops::resize(
+                frame,
+                frame.width() * factor,
+                frame.height() * factor,
+                FilterType::Nearest,
+            )),
+        }
+    }
+
+    /// Writes a grid of values as a frame, coloured by `palette`.
+    pub fn grid(&mut self, grid: &GrayImage, palette: &impl Palette) -> Result<()> {
+        self.draw(&Grid::new(grid, palette))
+    }
+
+    pub fn draw(&mut self, grid: &Grid) -> Result<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        let colors = grid.colorize();
+        let frame = self.scale(&colors);
+        for sink in &mut self.sinks {
+            sink.write_grid(grid, &frame)?;
+        }
+        Ok(())
+    }
+
+    /// Completes the output of the sinks, like the trailer of an animation.
+    pub fn finish(mut self) -> Result<()> {
+        for sink in &mut self.sinks {
+            sink.finish()?;
+        }
+        Ok(())
+    }
+}
+
+fn terminal(config: &TerminalConfig) -> Result<Box<dyn FrameSink>> {
+    let out: Box<dyn std::io::Write> = match &config.file {
+        Some(path) => Box::new(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("unable to open {}", path.display()))?,
+        ),
+        None => Box::new(std::io::stdout()),
+    };
+    let terminal = Terminal::new(out, config.colors);
+    if config.step {
+        Ok(Box::new(terminal.step_through()))
+    } else {
+        Ok(Box::new(terminal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+    use std::{cell::RefCell, rc::Rc};
+
+    /// Keeps the sizes of the frames written to it.
+    struct Sizes(Rc<RefCell<Vec<(u32, u32)>>>);
+
+    impl FrameSink for Sizes {
+        fn write(&mut self, frame: &RgbImage) -> Result<()> {
+            self.0.borrow_mut().push(frame.dimensions());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_grid() {
+        let values = GrayImage::from_raw(2, 2, vec![0, 1, 2, 3]).unwrap();
+        let palette = Grayscale::new(3);
+        let grid = Grid::new(&values, &palette).highlight(&[(1, 0), (5, 5)], Rgb([255, 0, 0]));
+        assert!(grid.is_highlighted(1, 0));
+        assert!(!grid.is_highlighted(0, 1));
+        assert_eq!(
+            vec![0, 0, 0, 255, 0, 0, 170, 170, 170, 255, 255, 255],
+            grid.colorize().into_raw()
+        );
+    }
+
+    #[test]
+    fn test_scaling() -> Result<()> {
+        let sizes = Rc::new(RefCell::new(vec![]));
+        let mut viz =
+            Visualizer::with_sinks(vec![Box::new(Sizes(sizes.clone()))], Scaling::Fit(256));
+        let palette = Grayscale::new(9);
+        viz.grid(&GrayImage::new(10, 10), &palette)?;
+        viz.grid(&GrayImage::new(100, 40), &palette)?;
+        viz.grid(&GrayImage::new(300, 2), &palette)?;
+        viz.grid(&GrayImage::new(3, 1), &palette)?;
+        viz.finish()?;
+        assert_eq!(
+            vec![(250, 250), (200, 80), (300, 2), (255, 85)],
+            *sizes.borrow()
+        );
+        assert_eq!(3, Scaling::Factor(3).factor(100, 100));
+        Ok(())
+    }
+
+    #[test]
+    fn test_disabled() -> Result<()> {
+        let mut viz = Visualizer::with_sinks(vec![], Scaling::Factor(2));
+        assert!(!viz.is_enabled());
+        viz.grid(&GrayImage::new(1, 1), &Grayscale::new(9))?;
+        viz.finish()
+    }
+
+    #[test]
+    fn test_sink_kinds() {
+        assert_eq!(SinkKind::Gif, "gif".parse().unwrap());
+        assert_eq!(SinkKind::Frames, "frames".parse().unwrap());
+        assert!("jpeg".parse::<SinkKind>().is_err());
+    }
+}
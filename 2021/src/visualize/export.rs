@@ -0,0 +1,218 @@
+//! Export of puzzle structure as documents for other tools: graphs as Graphviz DOT or SVG, and
+//! paths on grids as SVG, to be viewed in a browser.
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use image::{DynamicImage, ImageOutputFormat, Rgb};
+use petgraph::{dot::Dot, EdgeType, Graph};
+use std::{f64::consts::TAU, fmt, io::Cursor};
+
+use super::{config, Grid, Scaling};
+
+/// Writes `file_name` to the directory of the visualizations. Without the `visualize` feature
+/// nothing is written, and `contents` isn't rendered.
+pub(crate) fn save(file_name: &str, contents: impl FnOnce() -> Result<String>) -> Result<()> {
+    if !cfg!(feature = "visualize") {
+        return Ok(());
+    }
+    let dir = &config().dir;
+    std::fs::create_dir_all(dir).with_context(|| format!("unable to create {}", dir.display()))?;
+    let path = dir.join(file_name);
+    std::fs::write(&path, contents()?)
+        .with_context(|| format!("unable to write {}", path.display()))
+}
+
+/// How a node of a graph is drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NodeStyle {
+    pub label: String,
+    pub color: Rgb<u8>,
+}
+
+impl fmt::Display for NodeStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+fn hex(Rgb([r, g, b]): Rgb<u8>) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The graph as Graphviz DOT, with nodes filled in their style's colour.
+pub(crate) fn graph_dot<N, E, Ty: EdgeType>(
+    graph: &Graph<N, E, Ty>,
+    style: impl Fn(&N) -> NodeStyle,
+) -> String {
+    let styled = graph.map(|_, node| style(node), |_, _| "");
+    let dot = Dot::with_attr_getters(
+        &styled,
+        &[petgraph::dot::Config::EdgeNoLabel],
+        &|_, _| String::new(),
+        &|_, (_, style)| format!("style = filled fillcolor = \"{}\"", hex(style.color)),
+    );
+    dot.to_string()
+}
+
+/// The graph as SVG, with its nodes laid out on a circle.
+pub(crate) fn graph_svg<N, E, Ty: EdgeType>(
+    graph: &Graph<N, E, Ty>,
+    style: impl Fn(&N) -> NodeStyle,
+) -> String {
+    const NODE_RADIUS: f64 = 24.;
+    let radius = (graph.node_count() as f64 * NODE_RADIUS).max(100.);
+    let size = 2. * (radius + 2. * NODE_RADIUS);
+    let position = |i: usize| {
+        let angle = TAU * i as f64 / graph.node_count() as f64;
+        (
+            size / 2. + radius * angle.cos(),
+            size / 2. + radius * angle.sin(),
+        )
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" \
+         font-family=\"sans-serif\" font-size=\"12\" text-anchor=\"middle\">\n",
+        size
+    );
+    if Ty::is_directed() {
+        svg.push_str(&format!(
+            "<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"{}\" refY=\"5\" \
+             markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+             <path d=\"M 0 0 L 10 5 L 0 10 z\"/></marker></defs>\n",
+            10. + NODE_RADIUS * 10. / 6.
+        ));
+    }
+    for edge in graph.raw_edges() {
+        let (x1, y1) = position(edge.source().index());
+        let (x2, y2) = position(edge.target().index());
+        svg.push_str(&format!(
+            "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"black\"{}/>\n",
+            x1,
+            y1,
+            x2,
+            y2,
+            if Ty::is_directed() {
+                " marker-end=\"url(#arrow)\""
+            } else {
+                ""
+            }
+        ));
+    }
+    for (i, node) in graph.raw_nodes().iter().enumerate() {
+        let (x, y) = position(i);
+        let style = style(&node.weight);
+        svg.push_str(&format!(
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"{}\" stroke=\"black\"/>\
+             <text x=\"{:.1}\" y=\"{:.1}\" dy=\"0.35em\">{}</text>\n",
+            x,
+            y,
+            NODE_RADIUS,
+            hex(style.color),
+            x,
+            y,
+            escape(&style.label)
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// The grid as an image, with `path` drawn through the centres of its cells.
+pub(crate) fn path_svg(grid: &Grid, path: &[(u32, u32)], color: Rgb<u8>) -> Result<String> {
+    let cells = grid.colorize();
+    let (width, height) = cells.dimensions();
+    let factor = Scaling::Fit(1024).factor(width, height);
+    let mut png = Cursor::new(vec![]);
+    DynamicImage::ImageRgb8(cells).write_to(&mut png, ImageOutputFormat::Png)?;
+    let png = png.into_inner();
+    let points = path
+        .iter()
+        .map(|&(x, y)| format!("{}.5,{}.5", x, y))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // the grid is embedded as a PNG, so every cell doesn't need an element of its own
+    Ok(format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         viewBox=\"0 0 {} {}\">\n\
+         <image width=\"{}\" height=\"{}\" style=\"image-rendering: pixelated\" \
+         href=\"data:image/png;base64,{}\"/>\n\
+         <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.3\" \
+         stroke-linejoin=\"round\" stroke-linecap=\"round\"/>\n\
+         </svg>\n",
+        width * factor,
+        height * factor,
+        width,
+        height,
+        width,
+        height,
+        STANDARD.encode(&png),
+        points,
+        hex(color)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visualize::Grayscale;
+    use image::GrayImage;
+    use petgraph::Undirected;
+
+    fn graph() -> Graph<&'static str, (), Undirected> {
+        let mut graph = Graph::new_undirected();
+        let a = graph.add_node("a");
+        let b = graph.add_node("<b>");
+        graph.add_edge(a, b, ());
+        graph
+    }
+
+    fn style(name: &&str) -> NodeStyle {
+        NodeStyle {
+            label: name.to_string(),
+            color: Rgb([255, 0, 16]),
+        }
+    }
+
+    #[test]
+    fn test_graph_dot() {
+        let dot = graph_dot(&graph(), style);
+        assert!(dot.starts_with("graph {\n"));
+        assert!(dot.contains("0 [ label = \"a\" style = filled fillcolor = \"#ff0010\"]"));
+        assert!(dot.contains("0 -- 1 [ ]"));
+    }
+
+    #[test]
+    fn test_graph_svg() {
+        let svg = graph_svg(&graph(), style);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"296\""));
+        assert_eq!(1, svg.matches("<line ").count());
+        assert_eq!(2, svg.matches("<circle ").count());
+        assert!(svg.contains(">&lt;b&gt;</text>"));
+        assert!(!svg.contains("marker"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_path_svg() -> Result<()> {
+        let values = GrayImage::new(4, 2);
+        let palette = Grayscale::new(9);
+        let svg = path_svg(
+            &Grid::new(&values, &palette),
+            &[(0, 0), (1, 0), (1, 1)],
+            Rgb([255, 0, 0]),
+        )?;
+        assert!(svg.contains("width=\"1024\" height=\"512\" viewBox=\"0 0 4 2\""));
+        assert!(svg.contains("href=\"data:image/png;base64,iVBORw0KGgo"));
+        assert!(svg.contains("points=\"0.5,0.5 1.5,0.5 1.5,1.5\" fill=\"none\" stroke=\"#ff0000\""));
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+//! Golden image tests, comparing the frames of visualizations with reference images in
+//! `assets/golden`. Run the tests with `UPDATE_GOLDEN=1` to write the reference images instead.
+
+use anyhow::{bail, Context, Result};
+use image::RgbImage;
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+use super::{FrameSink, Scaling, Visualizer};
+
+/// Frames written by a visualizer.
+pub(crate) type Frames = Rc<RefCell<Vec<RgbImage>>>;
+
+struct Recorder(Frames);
+
+impl FrameSink for Recorder {
+    fn write(&mut self, frame: &RgbImage) -> Result<()> {
+        self.0.borrow_mut().push(frame.clone());
+        Ok(())
+    }
+}
+
+/// A visualizer keeping its frames unscaled.
+pub(crate) fn recorder() -> (Visualizer, Frames) {
+    let frames = Frames::default();
+    let sink = Box::new(Recorder(frames.clone()));
+    (
+        Visualizer::with_sinks(vec![sink], Scaling::Factor(1)),
+        frames,
+    )
+}
+
+pub(crate) fn disabled() -> Visualizer {
+    Visualizer::with_sinks(vec![], Scaling::Factor(1))
+}
+
+pub(crate) fn assert_golden(name: &str, frame: &RgbImage) -> Result<()> {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "assets", "golden"]
+        .iter()
+        .collect::<PathBuf>()
+        .join(format!("{}.png", name));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        frame.save(&path)?;
+        return Ok(());
+    }
+
+    let golden = image::open(&path)
+        .with_context(|| format!("unable to read {}", path.display()))?
+        .into_rgb8();
+    if golden.dimensions() != frame.dimensions() {
+        bail!(
+            "{}: frame of {:?} instead of {:?}",
+            name,
+            frame.dimensions(),
+            golden.dimensions()
+        );
+    }
+    let differences = golden
+        .enumerate_pixels()
+        .filter(|&(x, y, p)| frame.get_pixel(x, y) != p)
+        .map(|(x, y, _)| (x, y))
+        .collect::<Vec<_>>();
+    if let Some(first) = differences.first() {
+        bail!(
+            "{}: {} pixels differ from {}, the first at {:?}",
+            name,
+            differences.len(),
+            path.display(),
+            first
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,204 @@
+//! Colouring of grid values.
+//!
+//! All palettes are deterministic, so visualizations can be compared with reference images.
+
+use image::{GrayImage, Rgb, RgbImage};
+use palette::{FromColor, Gradient, Oklab, Oklch, Srgb};
+
+/// Maps the values of a grid to colours.
+pub(crate) trait Palette {
+    fn color(&self, value: u8) -> Rgb<u8>;
+
+    fn colorize(&self, grid: &GrayImage) -> RgbImage {
+        RgbImage::from_fn(grid.width(), grid.height(), |x, y| {
+            self.color(grid.get_pixel(x, y).0[0])
+        })
+    }
+}
+
+impl<F: Fn(u8) -> Rgb<u8>> Palette for F {
+    fn color(&self, value: u8) -> Rgb<u8> {
+        self(value)
+    }
+}
+
+/// Shades of grey from black at 0 to white at `max`, and white above it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Grayscale {
+    max: u8,
+}
+
+impl Grayscale {
+    pub fn new(max: u8) -> Self {
+        Self { max: max.max(1) }
+    }
+}
+
+impl Palette for Grayscale {
+    fn color(&self, value: u8) -> Rgb<u8> {
+        let v = (value.min(self.max) as u32 * 255 / self.max as u32) as u8;
+        Rgb([v, v, v])
+    }
+}
+
+/// A fixed colour per value, starting at `first`. Values outside of the colours are drawn with
+/// `background`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Indexed {
+    pub first: u8,
+    pub colors: Vec<Rgb<u8>>,
+    pub background: Rgb<u8>,
+}
+
+impl Palette for Indexed {
+    fn color(&self, value: u8) -> Rgb<u8> {
+        value
+            .checked_sub(self.first)
+            .and_then(|i| self.colors.get(i as usize))
+            .copied()
+            .unwrap_or(self.background)
+    }
+}
+
+fn to_rgb(color: Oklab) -> Rgb<u8> {
+    let (r, g, b) = Srgb::from_color(color)
+        .into_format::<u8>()
+        .into_components();
+    Rgb([r, g, b])
+}
+
+fn to_oklab(Rgb([r, g, b]): Rgb<u8>) -> Oklab {
+    Oklab::from_color(Srgb::new(r, g, b).into_format::<f32>())
+}
+
+/// The golden angle in degrees. Hues this far apart stay evenly spread, however many are taken.
+const GOLDEN_ANGLE: f32 = 137.507_77;
+
+/// `n` colours for telling categories apart. Their hues are spaced by the golden angle, starting
+/// from a hue given by `seed`, and they cycle through three levels of lightness so colours of
+/// similar hue still differ.
+pub(crate) fn categorical(n: usize, seed: u32) -> Vec<Rgb<u8>> {
+    const LIGHTNESS: [f32; 3] = [0.75, 0.6, 0.88];
+    let start = (seed % 360) as f32 * GOLDEN_ANGLE;
+    (0..n)
+        .map(|i| {
+            let hue = (start + i as f32 * GOLDEN_ANGLE) % 360.;
+            let color = Oklch::new(LIGHTNESS[i % LIGHTNESS.len()], 0.13, hue);
+            to_rgb(Oklab::from_color(color))
+        })
+        .collect()
+}
+
+/// A sequential palette from the first colour at 0 to the last one at `max`. The colours in
+/// between are interpolated in Oklab, which changes lightness evenly.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Ramp {
+    colors: Vec<Rgb<u8>>,
+}
+
+impl Ramp {
+    pub fn new(max: u8, stops: &[Rgb<u8>]) -> Self {
+        let gradient = Gradient::new(stops.iter().copied().map(to_oklab));
+        let max = max.max(1);
+        let colors = (0..=max)
+            .map(|v| to_rgb(gradient.get(v as f32 / max as f32)))
+            .collect();
+        Self { colors }
+    }
+
+    /// Black through red and yellow to white.
+    pub fn heat(max: u8) -> Self {
+        Self::new(
+            max,
+            &[
+                Rgb([0, 0, 0]),
+                Rgb([180, 0, 0]),
+                Rgb([255, 200, 0]),
+                Rgb([255, 255, 255]),
+            ],
+        )
+    }
+
+    /// Dark blue through green to yellow, close to matplotlib's viridis.
+    pub fn viridis(max: u8) -> Self {
+        Self::new(
+            max,
+            &[
+                Rgb([68, 1, 84]),
+                Rgb([59, 82, 139]),
+                Rgb([33, 145, 140]),
+                Rgb([94, 201, 98]),
+                Rgb([253, 231, 37]),
+            ],
+        )
+    }
+}
+
+impl Palette for Ramp {
+    fn color(&self, value: u8) -> Rgb<u8> {
+        self.colors[(value as usize).min(self.colors.len() - 1)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grayscale() {
+        let palette = Grayscale::new(9);
+        assert_eq!(Rgb([0, 0, 0]), palette.color(0));
+        assert_eq!(Rgb([85, 85, 85]), palette.color(3));
+        assert_eq!(Rgb([255, 255, 255]), palette.color(9));
+        assert_eq!(Rgb([255, 255, 255]), palette.color(200));
+    }
+
+    #[test]
+    fn test_indexed() {
+        let palette = Indexed {
+            first: 10,
+            colors: vec![Rgb([1, 0, 0]), Rgb([2, 0, 0])],
+            background: Rgb([0, 0, 0]),
+        };
+        let grid = GrayImage::from_raw(4, 1, vec![9, 10, 11, 12]).unwrap();
+        assert_eq!(
+            vec![0, 0, 0, 1, 0, 0, 2, 0, 0, 0, 0, 0],
+            palette.colorize(&grid).into_raw()
+        );
+        let inverted = |v: u8| Rgb([255 - v, 0, 0]);
+        assert_eq!(Rgb([245, 0, 0]), inverted.color(10));
+    }
+
+    #[test]
+    fn test_categorical() {
+        let colors = categorical(20, 0);
+        assert_eq!(colors, categorical(20, 0));
+        assert_eq!(colors[..5], categorical(5, 0)[..]);
+        assert_ne!(colors[0], categorical(1, 1)[0]);
+        // no two colours are alike
+        for (i, a) in colors.iter().enumerate() {
+            for b in &colors[i + 1..] {
+                let distance = to_oklab(*a) - to_oklab(*b);
+                let distance =
+                    (distance.l.powi(2) + distance.a.powi(2) + distance.b.powi(2)).sqrt();
+                assert!(distance > 0.03, "{:?} and {:?} are too similar", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ramp() {
+        let heat = Ramp::heat(3);
+        assert_eq!(Rgb([0, 0, 0]), heat.color(0));
+        assert_eq!(Rgb([255, 255, 255]), heat.color(3));
+        assert_eq!(Rgb([255, 255, 255]), heat.color(9));
+        let viridis = Ramp::viridis(255);
+        assert_eq!(Rgb([68, 1, 84]), viridis.color(0));
+        assert_eq!(Rgb([253, 231, 37]), viridis.color(255));
+        // lightness increases along the ramp
+        let lightness = (0..=255)
+            .map(|v| to_oklab(viridis.color(v)).l)
+            .collect::<Vec<_>>();
+        assert!(lightness.windows(2).all(|w| w[0] <= w[1] + 0.01));
+    }
+}
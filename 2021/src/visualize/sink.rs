@@ -0,0 +1,325 @@
+//! Destinations of visualized frames in files.
+
+use anyhow::{bail, Context, Result};
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, DynamicImage, Frame, RgbImage,
+};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use super::{Animation, Grid, Looping};
+
+/// Receives the frames of a visualization, one at a time.
+pub(crate) trait FrameSink {
+    fn write(&mut self, frame: &RgbImage) -> Result<()>;
+
+    /// Receives a grid along with the frame it was drawn as. Sinks that can show the cells of the
+    /// grid themselves override this.
+    fn write_grid(&mut self, _grid: &Grid, frame: &RgbImage) -> Result<()> {
+        self.write(frame)
+    }
+
+    /// Called after the last frame.
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn create_parent_dir(path: &Path) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("unable to create {}", dir.display()))?;
+    }
+    Ok(())
+}
+
+/// Writes every frame as `<name>.NNN.png`, or a single frame as `<name>.png`. The directory is
+/// created when the first file is written.
+pub(crate) struct PngSequence {
+    dir: PathBuf,
+    name: String,
+    frames: usize,
+    pending: Option<RgbImage>,
+}
+
+impl PngSequence {
+    pub fn new(dir: &Path, name: &str) -> Self {
+        Self {
+            dir: dir.to_owned(),
+            name: name.to_owned(),
+            frames: 0,
+            pending: None,
+        }
+    }
+
+    fn save(&self, frame: &RgbImage, file_name: String) -> Result<()> {
+        let path = self.dir.join(file_name);
+        create_parent_dir(&path)?;
+        frame
+            .save(&path)
+            .with_context(|| format!("unable to write {}", path.display()))
+    }
+
+    fn save_numbered(&mut self, frame: &RgbImage) -> Result<()> {
+        self.save(frame, format!("{}.{:03}.png", self.name, self.frames))?;
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+impl FrameSink for PngSequence {
+    fn write(&mut self, frame: &RgbImage) -> Result<()> {
+        // a frame is only written once it's known not to be the only one
+        if let Some(previous) = self.pending.replace(frame.clone()) {
+            self.save_numbered(&previous)?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        match self.pending.take() {
+            Some(frame) if self.frames == 0 => self.save(&frame, format!("{}.png", self.name)),
+            Some(frame) => self.save_numbered(&frame),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Writes all frames as an animated GIF. GIFs only support delays in steps of 10ms.
+pub(crate) struct Gif {
+    path: PathBuf,
+    animation: Animation,
+    encoder: Option<GifEncoder<BufWriter<File>>>,
+}
+
+impl Gif {
+    pub fn new(path: PathBuf, animation: Animation) -> Self {
+        Self {
+            path,
+            animation,
+            encoder: None,
+        }
+    }
+}
+
+impl FrameSink for Gif {
+    fn write(&mut self, frame: &RgbImage) -> Result<()> {
+        if self.encoder.is_none() {
+            create_parent_dir(&self.path)?;
+            let file = File::create(&self.path)
+                .with_context(|| format!("unable to create {}", self.path.display()))?;
+            let mut encoder = GifEncoder::new(BufWriter::new(file));
+            // GIFs count the repetitions after the first play
+            encoder.set_repeat(match self.animation.looping {
+                Looping::Forever => Repeat::Infinite,
+                Looping::Times(plays) => Repeat::Finite(plays.max(1) - 1),
+            })?;
+            self.encoder = Some(encoder);
+        }
+        let rgba = DynamicImage::ImageRgb8(frame.clone()).into_rgba8();
+        let delay = Delay::from_numer_denom_ms(self.animation.delay, 1);
+        self.encoder
+            .as_mut()
+            .unwrap()
+            .encode_frame(Frame::from_parts(rgba, 0, 0, delay))?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        // dropping the encoder writes the trailer
+        self.encoder.take();
+        Ok(())
+    }
+}
+
+/// Writes a single frame as a plain PNG, and several frames as an animated PNG (APNG). As the
+/// number of frames has to be known up front, all frames are kept until the end.
+pub(crate) struct Apng {
+    path: PathBuf,
+    animation: Animation,
+    frames: Vec<RgbImage>,
+}
+
+impl Apng {
+    pub fn new(path: PathBuf, animation: Animation) -> Self {
+        Self {
+            path,
+            animation,
+            frames: vec![],
+        }
+    }
+
+    fn encode(&self, out: impl Write) -> Result<()> {
+        let (width, height) = self.frames[0].dimensions();
+        let mut encoder = png::Encoder::new(out, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        if self.frames.len() > 1 {
+            let plays = match self.animation.looping {
+                Looping::Forever => 0,
+                Looping::Times(plays) => plays.max(1) as u32,
+            };
+            encoder.set_animated(self.frames.len() as u32, plays)?;
+            // longer delays are given in hundredths of a second instead
+            let delay = match u16::try_from(self.animation.delay) {
+                Ok(ms) => (ms, 1000),
+                Err(_) => (
+                    u16::try_from(self.animation.delay / 10).unwrap_or(u16::MAX),
+                    100,
+                ),
+            };
+            encoder.set_frame_delay(delay.0, delay.1)?;
+        }
+        let mut writer = encoder.write_header()?;
+        for frame in &self.frames {
+            writer.write_image_data(frame.as_raw())?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+impl FrameSink for Apng {
+    fn write(&mut self, frame: &RgbImage) -> Result<()> {
+        if let Some(first) = self.frames.first() {
+            if first.dimensions() != frame.dimensions() {
+                bail!(
+                    "frame of {:?} doesn't match the {:?} of the animation",
+                    frame.dimensions(),
+                    first.dimensions()
+                );
+            }
+        }
+        self.frames.push(frame.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if self.frames.is_empty() {
+            return Ok(());
+        }
+        create_parent_dir(&self.path)?;
+        let file = File::create(&self.path)
+            .with_context(|| format!("unable to create {}", self.path.display()))?;
+        self.encode(BufWriter::new(file))
+            .with_context(|| format!("unable to write {}", self.path.display()))?;
+        self.frames.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc2021_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn files(dir: &Path) -> Vec<String> {
+        let mut files = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_png_sequence() -> Result<()> {
+        let dir = temp_dir("png");
+        let frame = RgbImage::new(2, 2);
+
+        let mut still = PngSequence::new(&dir, "still");
+        assert!(!dir.exists());
+        still.write(&frame)?;
+        still.finish()?;
+        let mut animation = PngSequence::new(&dir, "anim");
+        for _ in 0..3 {
+            animation.write(&frame)?;
+        }
+        animation.finish()?;
+
+        assert_eq!(
+            vec!["anim.000.png", "anim.001.png", "anim.002.png", "still.png"],
+            files(&dir)
+        );
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_gif() -> Result<()> {
+        let dir = temp_dir("gif");
+        let path = dir.join("anim.gif");
+        let mut gif = Gif::new(
+            path.clone(),
+            Animation {
+                delay: 50,
+                looping: Looping::Times(2),
+            },
+        );
+        gif.finish()?;
+        assert!(!path.exists());
+
+        gif.write(&RgbImage::from_pixel(3, 2, Rgb([255, 0, 0])))?;
+        gif.write(&RgbImage::from_pixel(3, 2, Rgb([0, 0, 255])))?;
+        gif.finish()?;
+        let bytes = std::fs::read(&path)?;
+        assert_eq!(b"GIF89a", &bytes[..6]);
+        assert_eq!(Some(&0x3b), bytes.last());
+        // a single repetition in the NETSCAPE2.0 extension
+        let netscape = bytes.windows(11).position(|w| w == b"NETSCAPE2.0").unwrap();
+        assert_eq!(&[3, 1, 1, 0], &bytes[netscape + 11..netscape + 15]);
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    fn decode_png(path: &Path) -> Result<png::Reader<File>> {
+        Ok(png::Decoder::new(File::open(path)?).read_info()?)
+    }
+
+    #[test]
+    fn test_apng() -> Result<()> {
+        let dir = temp_dir("apng");
+        let animation = Animation {
+            delay: 40,
+            looping: Looping::Forever,
+        };
+
+        let mut still = Apng::new(dir.join("still.png"), animation);
+        still.write(&RgbImage::new(2, 2))?;
+        still.finish()?;
+        assert!(decode_png(&dir.join("still.png"))?
+            .info()
+            .animation_control
+            .is_none());
+
+        let path = dir.join("anim.png");
+        let mut anim = Apng::new(path.clone(), animation);
+        anim.write(&RgbImage::from_pixel(3, 2, Rgb([255, 0, 0])))?;
+        anim.write(&RgbImage::from_pixel(3, 2, Rgb([0, 0, 255])))?;
+        assert!(anim.write(&RgbImage::new(2, 3)).is_err());
+        anim.finish()?;
+
+        let mut reader = decode_png(&path)?;
+        let control = reader.info().animation_control.unwrap();
+        assert_eq!((2, 0), (control.num_frames, control.num_plays));
+        let mut buf = vec![0; reader.output_buffer_size()];
+        for color in [[255, 0, 0], [0, 0, 255]] {
+            reader.next_frame(&mut buf)?;
+            let frame = reader.info().frame_control.unwrap();
+            assert_eq!((40, 1000), (frame.delay_num, frame.delay_den));
+            assert_eq!(&color, &buf[..3]);
+        }
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+//! Drawing of frames and grids with ANSI escape sequences.
+
+use anyhow::{bail, Result};
+use image::{Rgb, RgbImage};
+use std::{
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+use super::{FrameSink, Grid};
+
+/// The colours used on the terminal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorMode {
+    /// 24 bit colours.
+    TrueColor,
+    /// The 256 colour palette of xterm, for terminals without true colour.
+    Ansi256,
+}
+
+impl FromStr for ColorMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "truecolor" => Ok(ColorMode::TrueColor),
+            "256" => Ok(ColorMode::Ansi256),
+            _ => bail!("unknown colour mode '{}', expected truecolor or 256", s),
+        }
+    }
+}
+
+impl ColorMode {
+    fn foreground(self, color: Rgb<u8>) -> String {
+        format!("\x1b[38;{}m", self.color(color))
+    }
+
+    fn background(self, color: Rgb<u8>) -> String {
+        format!("\x1b[48;{}m", self.color(color))
+    }
+
+    fn color(self, color: Rgb<u8>) -> String {
+        match self {
+            ColorMode::TrueColor => {
+                let Rgb([r, g, b]) = color;
+                format!("2;{};{};{}", r, g, b)
+            }
+            ColorMode::Ansi256 => format!("5;{}", ansi256(color)),
+        }
+    }
+}
+
+/// The nearest colour of the 6x6x6 cube of the 256 colour palette, or of its grey ramp for greys.
+fn ansi256(Rgb([r, g, b]): Rgb<u8>) -> u8 {
+    if r == g && g == b {
+        // the ramp runs from 8 to 238 in steps of 10, black and white are part of the cube
+        match r {
+            0..=3 => 16,
+            247..=255 => 231,
+            v => 232 + ((v as u16 - 3) / 10).min(23) as u8,
+        }
+    } else {
+        let level = |v: u8| ((v as u16 * 5 + 127) / 255) as u8;
+        16 + 36 * level(r) + 6 * level(g) + level(b)
+    }
+}
+
+/// Draws on a terminal, or into a file to be replayed with `cat`. Frames are drawn with half
+/// blocks, two rows of pixels per line of text, while grids with glyphs get a character per cell.
+/// Each frame is drawn from the top left corner, on top of the previous one.
+pub(crate) struct Terminal<W: Write> {
+    out: W,
+    colors: ColorMode,
+    step: bool,
+    frames: usize,
+}
+
+impl<W: Write> Terminal<W> {
+    pub fn new(out: W, colors: ColorMode) -> Self {
+        Self {
+            out,
+            colors,
+            step: false,
+            frames: 0,
+        }
+    }
+
+    /// Waits for enter after every frame.
+    pub fn step_through(mut self) -> Self {
+        self.step = true;
+        self
+    }
+
+    #[cfg(test)]
+    fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn show(&mut self, text: String) -> Result<()> {
+        // clearing the screen only once avoids flickering
+        let clear = if self.frames == 0 { "\x1b[2J" } else { "" };
+        write!(self.out, "{}\x1b[H{}", clear, text)?;
+        self.frames += 1;
+        if self.step {
+            write!(self.out, "frame {}, press enter to continue", self.frames)?;
+            self.out.flush()?;
+            std::io::stdin().lock().read_line(&mut String::new())?;
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+
+    fn blocks(&self, frame: &RgbImage) -> String {
+        let black = Rgb([0, 0, 0]);
+        let mut text = String::new();
+        for y in (0..frame.height()).step_by(2) {
+            for x in 0..frame.width() {
+                let lower = if y + 1 < frame.height() {
+                    *frame.get_pixel(x, y + 1)
+                } else {
+                    black
+                };
+                text.push_str(&self.colors.foreground(*frame.get_pixel(x, y)));
+                text.push_str(&self.colors.background(lower));
+                text.push('▀');
+            }
+            text.push_str("\x1b[0m\n");
+        }
+        text
+    }
+
+    fn glyphs(&self, grid: &Grid, glyph: &dyn Fn(u8) -> char) -> String {
+        let colors = grid.colorize();
+        let mut text = String::new();
+        for y in 0..colors.height() {
+            for x in 0..colors.width() {
+                let color = *colors.get_pixel(x, y);
+                if grid.is_highlighted(x, y) {
+                    text.push_str(&self.colors.background(color));
+                    text.push_str(&self.colors.foreground(Rgb([0, 0, 0])));
+                } else {
+                    text.push_str("\x1b[49m");
+                    text.push_str(&self.colors.foreground(color));
+                }
+                text.push(glyph(grid.value(x, y)));
+            }
+            text.push_str("\x1b[0m\n");
+        }
+        text
+    }
+}
+
+impl<W: Write> FrameSink for Terminal<W> {
+    fn write(&mut self, frame: &RgbImage) -> Result<()> {
+        let text = self.blocks(frame);
+        self.show(text)
+    }
+
+    /// Grids are drawn unscaled, as characters already are bigger than pixels.
+    fn write_grid(&mut self, grid: &Grid, _frame: &RgbImage) -> Result<()> {
+        let text = match grid.glyph_fn() {
+            Some(glyph) => self.glyphs(grid, glyph),
+            None => self.blocks(&grid.colorize()),
+        };
+        self.show(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visualize::Grayscale;
+    use image::GrayImage;
+
+    #[test]
+    fn test_ansi256() {
+        assert_eq!(16, ansi256(Rgb([0, 0, 0])));
+        assert_eq!(231, ansi256(Rgb([255, 255, 255])));
+        assert_eq!(244, ansi256(Rgb([128, 128, 128])));
+        assert_eq!(196, ansi256(Rgb([255, 0, 0])));
+        assert_eq!(21, ansi256(Rgb([0, 10, 250])));
+        assert_eq!(
+            "\x1b[38;5;196m",
+            ColorMode::Ansi256.foreground(Rgb([255, 0, 0]))
+        );
+        assert_eq!(ColorMode::Ansi256, "256".parse().unwrap());
+        assert!("16".parse::<ColorMode>().is_err());
+    }
+
+    #[test]
+    fn test_frames() -> Result<()> {
+        let mut terminal = Terminal::new(vec![], ColorMode::TrueColor);
+        let mut frame = RgbImage::new(1, 3);
+        frame.put_pixel(0, 0, Rgb([1, 2, 3]));
+        frame.put_pixel(0, 1, Rgb([4, 5, 6]));
+        frame.put_pixel(0, 2, Rgb([7, 8, 9]));
+        terminal.write(&frame)?;
+        terminal.write(&RgbImage::new(1, 1))?;
+        assert_eq!(
+            "\x1b[2J\x1b[H\
+             \x1b[38;2;1;2;3m\x1b[48;2;4;5;6m▀\x1b[0m\n\
+             \x1b[38;2;7;8;9m\x1b[48;2;0;0;0m▀\x1b[0m\n\
+             \x1b[H\
+             \x1b[38;2;0;0;0m\x1b[48;2;0;0;0m▀\x1b[0m\n",
+            String::from_utf8(terminal.into_inner())?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_glyphs() -> Result<()> {
+        let mut terminal = Terminal::new(vec![], ColorMode::Ansi256);
+        let values = GrayImage::from_raw(3, 1, vec![0, 5, 9]).unwrap();
+        let palette = Grayscale::new(9);
+        let digit = |v: u8| char::from_digit(v as u32, 10).unwrap();
+        let grid = Grid::new(&values, &palette)
+            .glyphs(&digit)
+            .highlight(&[(1, 0)], Rgb([255, 0, 0]));
+        terminal.write_grid(&grid, &RgbImage::new(30, 10))?;
+        assert_eq!(
+            "\x1b[2J\x1b[H\
+             \x1b[49m\x1b[38;5;16m0\
+             \x1b[48;5;196m\x1b[38;5;16m5\
+             \x1b[49m\x1b[38;5;231m9\x1b[0m\n",
+            String::from_utf8(terminal.into_inner())?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use std::str::Lines;
+
+#[allow(unused)]
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::{
+    input::{parse_records, ParseError},
+    select::top_k,
+};
+
+/// Total calories carried by each elf.
+fn calories(input: Lines) -> Result<Vec<usize>, ParseError> {
+    Ok(parse_records::<usize>(input)?
+        .into_iter()
+        .map(|items| items.into_iter().sum())
+        .collect())
+}
+
+pub fn task1(input: Lines) -> Result<usize, ParseError> {
+    Ok(top_k(calories(input)?, 1).into_iter().sum())
+}
+
+pub fn task2(input: Lines) -> Result<usize, ParseError> {
+    Ok(top_k(calories(input)?, 3).into_iter().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use tracing_test::traced_test;
+
+    const INPUT: &str = indoc! {r#"
+        1000
+        2000
+        3000
+
+        4000
+
+        5000
+        6000
+
+        7000
+        8000
+        9000
+
+        10000
+    "#};
+
+    #[test]
+    #[traced_test]
+    fn task1_example() {
+        assert_eq!(
+            calories(INPUT.lines()),
+            Ok(vec![6000, 4000, 11000, 24000, 10000])
+        );
+        assert_eq!(task1(INPUT.lines()), Ok(24000));
+    }
+
+    #[test]
+    #[traced_test]
+    fn task2_example() {
+        assert_eq!(task2(INPUT.lines()), Ok(45000));
+    }
+
+    #[test]
+    fn invalid_calories() {
+        assert_eq!(
+            task1("1000\n\n2000\n20OO\n3000".lines()),
+            Err(ParseError {
+                line: 4,
+                text: "20OO".to_owned()
+            })
+        );
+    }
+}
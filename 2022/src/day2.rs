@@ -0,0 +1,328 @@
+use std::str::Lines;
+
+use derive_more::{Display, Error};
+use itertools::Itertools;
+#[allow(unused)]
+use tracing::{debug, error, info, instrument, warn};
+
+/// A shape, identified by its position in the cycle of a [`CyclicGame`].
+#[derive(PartialEq, Debug, Clone, Copy)]
+struct Shape(usize);
+
+/// A game where the shapes form a cycle, and every shape beats the half of the other shapes that
+/// precede it in the cycle. The number of shapes must be odd, so no two different shapes draw.
+///
+/// Ordering the shapes as Rock, Paper, Scissor gives the classic game, and Rock, Spock, Paper,
+/// Lizard, Scissor gives Rock-Paper-Scissor-Lizard-Spock.
+#[derive(Debug, Clone)]
+pub struct CyclicGame<'a> {
+    /// Symbols used for the opponents shapes in the strategy guide, in cycle order.
+    opponent_symbols: &'a str,
+    /// Symbols used for the second column of the strategy guide, in cycle order.
+    response_symbols: &'a str,
+}
+
+#[derive(Debug, Display, Error, PartialEq, Clone)]
+pub enum GameError {
+    #[display(
+        fmt = "a game needs an odd number of at least 3 shapes, found {}",
+        count
+    )]
+    Shapes { count: usize },
+    #[display(
+        fmt = "{} opponent symbols but {} response symbols",
+        opponent,
+        response
+    )]
+    SymbolCounts { opponent: usize, response: usize },
+    #[display(fmt = "symbol {} stands for more than one shape", symbol)]
+    DuplicateSymbol { symbol: char },
+}
+
+impl<'a> CyclicGame<'a> {
+    pub const ROCK_PAPER_SCISSOR: CyclicGame<'static> = CyclicGame {
+        opponent_symbols: "ABC",
+        response_symbols: "XYZ",
+    };
+
+    /// A game of the shapes written as `opponent_symbols` and `response_symbols` in the strategy
+    /// guide, both in cycle order.
+    pub fn new(opponent_symbols: &'a str, response_symbols: &'a str) -> Result<Self, GameError> {
+        let (opponent, response) = (
+            opponent_symbols.chars().count(),
+            response_symbols.chars().count(),
+        );
+        if opponent != response {
+            return Err(GameError::SymbolCounts { opponent, response });
+        }
+        if opponent < 3 || opponent % 2 == 0 {
+            return Err(GameError::Shapes { count: opponent });
+        }
+        for symbols in [opponent_symbols, response_symbols] {
+            if let Some(symbol) = symbols.chars().duplicates().next() {
+                return Err(GameError::DuplicateSymbol { symbol });
+            }
+        }
+        Ok(Self {
+            opponent_symbols,
+            response_symbols,
+        })
+    }
+
+    fn shapes(&self) -> usize {
+        self.opponent_symbols.chars().count()
+    }
+
+    fn battle(&self, me: Shape, other: Shape) -> BattleResult {
+        let n = self.shapes();
+        match (me.0 + n - other.0) % n {
+            0 => BattleResult::Draw,
+            d if d <= n / 2 => BattleResult::Win,
+            _ => BattleResult::Loose,
+        }
+    }
+
+    /// Picks a shape giving the `expected` result against `opponent`; the nearest one in the
+    /// cycle when there are several.
+    fn pick_selection(&self, expected: BattleResult, opponent: Shape) -> Shape {
+        let n = self.shapes();
+        match expected {
+            BattleResult::Draw => opponent,
+            BattleResult::Win => Shape((opponent.0 + 1) % n),
+            BattleResult::Loose => Shape((opponent.0 + n - 1) % n),
+        }
+    }
+
+    fn score(&self, me: Shape, other: Shape) -> usize {
+        self.battle(me, other).score() + me.0 + 1
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum BattleResult {
+    Win,
+    Loose,
+    Draw,
+}
+
+impl BattleResult {
+    pub fn score(self) -> usize {
+        match self {
+            BattleResult::Win => 6,
+            BattleResult::Loose => 0,
+            BattleResult::Draw => 3,
+        }
+    }
+}
+
+/// How the second column of the strategy guide is read.
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Interpretation {
+    /// The shape to respond with.
+    Shape,
+    /// Whether to loose, draw or win; the first three response symbols, in that order.
+    Outcome,
+}
+
+#[derive(Debug, Display, Error, PartialEq, Clone)]
+#[display(fmt = "line {}: {}", line, kind)]
+pub struct ParseError {
+    pub line: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Display, PartialEq, Clone)]
+pub enum ParseErrorKind {
+    #[display(fmt = "expected 2 columns, found {}", _0)]
+    Columns(usize),
+    #[display(fmt = "unknown shape '{}'", _0)]
+    UnknownShape(String),
+    #[display(fmt = "unknown outcome '{}'", _0)]
+    UnknownOutcome(String),
+}
+
+fn symbol_position(symbols: &str, symbol: &str) -> Option<usize> {
+    let mut chars = symbol.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => symbols.chars().position(|s| s == c),
+        _ => None,
+    }
+}
+
+/// Reads the strategy guide as rounds of opponent shape and the shape to respond with.
+fn parse_guide(
+    game: &CyclicGame,
+    input: Lines,
+    interpretation: Interpretation,
+) -> Result<Vec<(Shape, Shape)>, ParseError> {
+    input
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(n, line)| {
+            let error = |kind| ParseError { line: n + 1, kind };
+            let cols = line.split_whitespace().collect::<Vec<_>>();
+            let [opponent, response] = cols[..] else {
+                return Err(error(ParseErrorKind::Columns(cols.len())));
+            };
+            let opponent = symbol_position(game.opponent_symbols, opponent)
+                .map(Shape)
+                .ok_or_else(|| error(ParseErrorKind::UnknownShape(opponent.to_owned())))?;
+            let me = match interpretation {
+                Interpretation::Shape => symbol_position(game.response_symbols, response)
+                    .map(Shape)
+                    .ok_or_else(|| error(ParseErrorKind::UnknownShape(response.to_owned())))?,
+                Interpretation::Outcome => {
+                    let expected = match symbol_position(game.response_symbols, response) {
+                        Some(0) => BattleResult::Loose,
+                        Some(1) => BattleResult::Draw,
+                        Some(2) => BattleResult::Win,
+                        _ => {
+                            return Err(error(ParseErrorKind::UnknownOutcome(response.to_owned())))
+                        }
+                    };
+                    game.pick_selection(expected, opponent)
+                }
+            };
+            Ok((opponent, me))
+        })
+        .collect()
+}
+
+/// Total score of following the strategy guide.
+fn evaluate(
+    game: &CyclicGame,
+    input: Lines,
+    interpretation: Interpretation,
+) -> Result<usize, ParseError> {
+    Ok(parse_guide(game, input, interpretation)?
+        .into_iter()
+        .map(|(opponent, me)| game.score(me, opponent))
+        .sum())
+}
+
+pub fn task1(game: &CyclicGame, input: Lines) -> Result<usize, ParseError> {
+    evaluate(game, input, Interpretation::Shape)
+}
+
+pub fn task2(game: &CyclicGame, input: Lines) -> Result<usize, ParseError> {
+    evaluate(game, input, Interpretation::Outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use tracing_test::traced_test;
+
+    const ROCK: Shape = Shape(0);
+    const PAPER: Shape = Shape(1);
+    const SCISSOR: Shape = Shape(2);
+
+    const INPUT: &str = indoc! {r#"
+        A Y
+        B X
+        C Z
+    "#};
+
+    #[test]
+    #[traced_test]
+    fn task1_example() {
+        let game = CyclicGame::ROCK_PAPER_SCISSOR;
+        assert_eq!(
+            parse_guide(&game, INPUT.lines(), Interpretation::Shape),
+            Ok(vec![(ROCK, PAPER), (PAPER, ROCK), (SCISSOR, SCISSOR)])
+        );
+
+        assert_eq!(task1(&game, INPUT.lines()), Ok(15));
+    }
+
+    #[test]
+    #[traced_test]
+    fn parser2() {
+        let game = CyclicGame::ROCK_PAPER_SCISSOR;
+        assert_eq!(
+            parse_guide(&game, INPUT.lines(), Interpretation::Outcome),
+            Ok(vec![(ROCK, ROCK), (PAPER, ROCK), (SCISSOR, ROCK)])
+        );
+        assert_eq!(task2(&game, INPUT.lines()), Ok(12));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let game = CyclicGame::ROCK_PAPER_SCISSOR;
+        let error = |input: &str| task1(&game, input.lines()).unwrap_err();
+        assert_eq!(error("A Y\n\nD X").to_string(), "line 3: unknown shape 'D'");
+        assert_eq!(error("A").kind, ParseErrorKind::Columns(1));
+        assert_eq!(
+            error("A XY").kind,
+            ParseErrorKind::UnknownShape("XY".to_owned())
+        );
+        assert_eq!(
+            task2(&game, "B W".lines()).unwrap_err().kind,
+            ParseErrorKind::UnknownOutcome("W".to_owned())
+        );
+    }
+
+    #[test]
+    fn invalid_games() {
+        assert_eq!(
+            CyclicGame::new("ABCD", "WXYZ").unwrap_err(),
+            GameError::Shapes { count: 4 }
+        );
+        assert_eq!(
+            CyclicGame::new("A", "X").unwrap_err(),
+            GameError::Shapes { count: 1 }
+        );
+        assert_eq!(
+            CyclicGame::new("ABC", "XY").unwrap_err().to_string(),
+            "3 opponent symbols but 2 response symbols"
+        );
+        assert_eq!(
+            CyclicGame::new("AAB", "XYZ").unwrap_err(),
+            GameError::DuplicateSymbol { symbol: 'A' }
+        );
+        assert_eq!(
+            CyclicGame::new("ABC", "XYX").unwrap_err().to_string(),
+            "symbol X stands for more than one shape"
+        );
+        assert!(CyclicGame::new("ABC", "XYZ").is_ok());
+    }
+
+    #[test]
+    fn rock_paper_scissor_lizard_spock() {
+        // rock, spock, paper, lizard, scissor
+        let game = CyclicGame::new("RKPLS", "rkpls").unwrap();
+        let beats = |a: &str, b: &str| {
+            let a = Shape(symbol_position(game.response_symbols, a).unwrap());
+            let b = Shape(symbol_position(game.response_symbols, b).unwrap());
+            game.battle(a, b)
+        };
+        for (winner, looser) in [
+            ("s", "p"),
+            ("p", "r"),
+            ("r", "l"),
+            ("l", "k"),
+            ("k", "s"),
+            ("s", "l"),
+            ("l", "p"),
+            ("p", "k"),
+            ("k", "r"),
+            ("r", "s"),
+        ] {
+            assert_eq!(beats(winner, looser), BattleResult::Win);
+            assert_eq!(beats(looser, winner), BattleResult::Loose);
+        }
+        assert_eq!(beats("k", "k"), BattleResult::Draw);
+
+        // lizard (4) wins, spock (2) draws and rock (1) looses against spock
+        assert_eq!(
+            task1(&game, "K l\nK k\nK r".lines()),
+            Ok((4 + 6) + (2 + 3) + 1)
+        );
+        // loosing with paper (3), drawing with lizard (4) and winning with scissor (5) against lizard
+        assert_eq!(
+            task2(&game, "L r\nL k\nL p".lines()),
+            Ok(3 + (4 + 3) + (5 + 6))
+        );
+    }
+}
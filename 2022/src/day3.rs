@@ -0,0 +1,239 @@
+use std::str::Lines;
+
+use derive_more::{Display, Error};
+use itertools::Itertools;
+#[allow(unused)]
+use tracing::{debug, error, info, instrument, warn};
+
+/// A set of items, stored as one bit per item at the position of its priority minus one.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+struct ItemSet(u64);
+
+impl ItemSet {
+    const ALL: ItemSet = ItemSet((1 << 52) - 1);
+
+    fn priority(item: char) -> Option<usize> {
+        match item {
+            'a'..='z' => Some((item as u8 - b'a') as usize + 1),
+            'A'..='Z' => Some((item as u8 - b'A') as usize + 27),
+            _ => None,
+        }
+    }
+
+    fn item(priority: usize) -> char {
+        match priority {
+            1..=26 => (b'a' + (priority - 1) as u8) as char,
+            _ => (b'A' + (priority - 27) as u8) as char,
+        }
+    }
+
+    /// Builds the set from a list of items, returning the first invalid item on failure.
+    fn parse(items: &str) -> Result<Self, char> {
+        items.chars().try_fold(ItemSet::default(), |set, c| {
+            let priority = ItemSet::priority(c).ok_or(c)?;
+            Ok(ItemSet(set.0 | 1 << (priority - 1)))
+        })
+    }
+
+    fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    fn priorities(self) -> impl Iterator<Item = usize> {
+        (0..52)
+            .filter(move |bit| self.0 & 1 << bit != 0)
+            .map(|bit| bit + 1)
+    }
+
+    fn items(self) -> String {
+        self.priorities().map(ItemSet::item).collect()
+    }
+}
+
+#[derive(Debug, Display, Error, PartialEq, Clone)]
+pub enum Error {
+    #[display(fmt = "line {}: invalid item '{}'", line, item)]
+    InvalidItem { line: usize, item: char },
+    #[display(fmt = "line {}: odd number of items ({})", line, count)]
+    OddLength { line: usize, count: usize },
+    #[display(fmt = "line {}: incomplete group of {} rucksacks", line, size)]
+    IncompleteGroup { line: usize, size: usize },
+    #[display(fmt = "groups of 0 rucksacks have no badge")]
+    EmptyGroup,
+    #[display(fmt = "line {}: group shares no item", line)]
+    NoCommonItem { line: usize },
+    #[display(fmt = "line {}: group shares several items '{}'", line, items)]
+    MultipleCommonItems { line: usize, items: String },
+}
+
+/// Non-blank lines of the input, with their line numbers.
+fn rucksacks(input: Lines<'_>) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .enumerate()
+        .map(|(n, l)| (n + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty())
+}
+
+fn parse_items(line: usize, items: &str) -> Result<ItemSet, Error> {
+    ItemSet::parse(items).map_err(|item| Error::InvalidItem { line, item })
+}
+
+/// Finds the single item shared by all sets of a group starting at `line`.
+fn common_item(line: usize, group: impl IntoIterator<Item = ItemSet>) -> Result<usize, Error> {
+    let common = group.into_iter().fold(ItemSet::ALL, ItemSet::intersection);
+    match common.len() {
+        0 => Err(Error::NoCommonItem { line }),
+        1 => Ok(common.priorities().next().unwrap()),
+        _ => Err(Error::MultipleCommonItems {
+            line,
+            items: common.items(),
+        }),
+    }
+}
+
+pub fn task1(input: Lines) -> Result<usize, Error> {
+    rucksacks(input)
+        .map(|(line, items)| {
+            // only ASCII items are valid, so the line can be split at any byte after this
+            parse_items(line, items)?;
+            if items.len() % 2 != 0 {
+                return Err(Error::OddLength {
+                    line,
+                    count: items.len(),
+                });
+            }
+            let (a, b) = items.split_at(items.len() / 2);
+            common_item(line, [parse_items(line, a)?, parse_items(line, b)?])
+        })
+        .sum()
+}
+
+pub fn task2(input: Lines) -> Result<usize, Error> {
+    Ok(find_badges(input, 3)?
+        .into_iter()
+        .filter_map(ItemSet::priority)
+        .sum())
+}
+
+/// Finds the item shared by every group of `group_size` consecutive rucksacks.
+pub fn find_badges(input: Lines, group_size: usize) -> Result<Vec<char>, Error> {
+    if group_size == 0 {
+        return Err(Error::EmptyGroup);
+    }
+    rucksacks(input)
+        .chunks(group_size)
+        .into_iter()
+        .map(|chunk| {
+            let group = chunk
+                .map(|(line, items)| Ok((line, parse_items(line, items)?)))
+                .collect::<Result<Vec<_>, Error>>()?;
+            let line = group[0].0;
+            if group.len() < group_size {
+                return Err(Error::IncompleteGroup {
+                    line,
+                    size: group.len(),
+                });
+            }
+            common_item(line, group.into_iter().map(|(_, set)| set)).map(ItemSet::item)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use tracing_test::traced_test;
+
+    const INPUT: &str = indoc! {r#"
+        vJrwpWtwJgWrhcsFMMfFFhFp
+        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+        PmmdzqPrVvPwwTWBwg
+        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+        ttgJtRGJQctTZtZT
+        CrZsJsPPZsGzwwsLwLmpwMDw
+    "#};
+
+    #[test]
+    fn priority() {
+        assert_eq!(Some(1), ItemSet::priority('a'));
+        assert_eq!(Some(26), ItemSet::priority('z'));
+        assert_eq!(Some(27), ItemSet::priority('A'));
+        assert_eq!(Some(52), ItemSet::priority('Z'));
+        assert_eq!(None, ItemSet::priority('1'));
+        assert_eq!(
+            ItemSet::ALL.items(),
+            ('a'..='z').chain('A'..='Z').collect::<String>()
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn task1_example() {
+        assert_eq!(task1(INPUT.lines()), Ok(157));
+    }
+
+    #[test]
+    #[traced_test]
+    fn task2_example() {
+        assert_eq!(find_badges(INPUT.lines(), 3), Ok(vec!['r', 'Z']));
+        assert_eq!(task2(INPUT.lines()), Ok(70));
+    }
+
+    #[test]
+    fn group_sizes() {
+        assert_eq!(
+            find_badges("abc\ncde\nxA\nAy".lines(), 2),
+            Ok(vec!['c', 'A'])
+        );
+        assert!(find_badges("aBc\nBd".lines(), 1).is_err());
+        assert_eq!(find_badges("aBc\nBd".lines(), 0), Err(Error::EmptyGroup));
+        assert_eq!(
+            find_badges("aBc\nB".lines(), 6).unwrap_err().to_string(),
+            "line 1: incomplete group of 2 rucksacks"
+        );
+        assert_eq!(
+            find_badges("aB\naBc\nBda\n".lines(), 3),
+            Err(Error::MultipleCommonItems {
+                line: 1,
+                items: "aB".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            task1("abca\n\nabc".lines()),
+            Err(Error::OddLength { line: 3, count: 3 })
+        );
+        assert_eq!(
+            task1("ab1b".lines()),
+            Err(Error::InvalidItem { line: 1, item: '1' })
+        );
+        assert_eq!(
+            task1("aéb".lines()),
+            Err(Error::InvalidItem {
+                line: 1, item: 'é'
+            })
+        );
+        assert_eq!(
+            task1("aébé".lines()),
+            Err(Error::InvalidItem {
+                line: 1, item: 'é'
+            })
+        );
+        assert_eq!(
+            task1("abcd".lines()).unwrap_err().to_string(),
+            "line 1: group shares no item"
+        );
+        assert_eq!(
+            task2("ab\ncd\nef".lines()),
+            Err(Error::NoCommonItem { line: 1 })
+        );
+    }
+}
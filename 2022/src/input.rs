@@ -0,0 +1,93 @@
+//! Splitting and parsing of puzzle inputs, shared between the days.
+//!
+//! Line numbers count from 1, and are kept around so errors can point at the offending line.
+
+use std::str::FromStr;
+
+use derive_more::{Display, Error};
+
+#[derive(Debug, Display, Error, PartialEq, Clone)]
+#[display(fmt = "line {}: unable to parse '{}'", line, text)]
+pub struct ParseError {
+    pub line: usize,
+    pub text: String,
+}
+
+/// The lines of a record, with their line numbers.
+pub type Record<'a> = Vec<(usize, &'a str)>;
+
+/// Splits the input into records of consecutive non-blank lines. Records are separated by one or
+/// more blank lines; lines are trimmed of surrounding whitespace.
+pub fn records<'a>(lines: impl IntoIterator<Item = &'a str>) -> impl Iterator<Item = Record<'a>> {
+    let mut lines = lines
+        .into_iter()
+        .enumerate()
+        .map(|(n, l)| (n + 1, l.trim()))
+        .peekable();
+    std::iter::from_fn(move || {
+        while lines.next_if(|(_, l)| l.is_empty()).is_some() {}
+        let mut record = vec![];
+        while let Some(line) = lines.next_if(|(_, l)| !l.is_empty()) {
+            record.push(line);
+        }
+        (!record.is_empty()).then_some(record)
+    })
+}
+
+/// Parses every line of every record, failing on the first line that doesn't parse.
+pub fn parse_records<'a, T: FromStr>(
+    lines: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    records(lines)
+        .map(|record| {
+            record
+                .into_iter()
+                .map(|(line, text)| {
+                    text.parse().map_err(|_| ParseError {
+                        line,
+                        text: text.to_owned(),
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn split_records() {
+        let input = indoc! {"
+
+            a
+             b
+
+            c
+
+
+            d
+        "};
+        assert_eq!(
+            records(input.lines()).collect::<Vec<_>>(),
+            vec![vec![(2, "a"), (3, "b")], vec![(5, "c")], vec![(8, "d")]]
+        );
+        assert_eq!(records("\n \n".lines()).count(), 0);
+    }
+
+    #[test]
+    fn strict_parsing() {
+        assert_eq!(
+            parse_records::<u32>("1\n2\n\n3".lines()),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(
+            parse_records::<u32>("1\n\n2\nx\n3".lines())
+                .unwrap_err()
+                .to_string(),
+            "line 4: unable to parse 'x'"
+        );
+    }
+}
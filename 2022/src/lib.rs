@@ -0,0 +1,105 @@
+//! Solutions for [Advent of Code 2022](https://adventofcode.com/2022).
+//!
+//! Every day lives in its own module, and is registered in [`PUZZLES`] so it can be looked up
+//! by its number.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+
+pub mod input;
+pub mod select;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+
+/// Solves one part of a puzzle from the puzzle input, returning the answer.
+pub type Solver = fn(&str) -> Result<String>;
+
+/// A registered puzzle.
+pub struct Puzzle {
+    pub day: u32,
+    pub parts: [Solver; 2],
+}
+
+impl Puzzle {
+    /// Solves `part`, counting from 1.
+    pub fn solve(&self, part: usize, input: &str) -> Result<String> {
+        let Some(solver) = part.checked_sub(1).and_then(|i| self.parts.get(i)) else {
+            bail!("day {} has no part {}", self.day, part);
+        };
+        solver(input).with_context(|| format!("day {}, part {}", self.day, part))
+    }
+
+    /// The default location of the puzzle input, `assets/dayN.txt` in the crate directory.
+    pub fn input_path(&self) -> PathBuf {
+        [
+            env!("CARGO_MANIFEST_DIR"),
+            "assets",
+            &format!("day{}.txt", self.day),
+        ]
+        .iter()
+        .collect()
+    }
+}
+
+/// All solved puzzles, ordered by day.
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle {
+        day: 1,
+        parts: [
+            |input| Ok(day1::task1(input.lines())?.to_string()),
+            |input| Ok(day1::task2(input.lines())?.to_string()),
+        ],
+    },
+    Puzzle {
+        day: 2,
+        parts: [
+            |input| {
+                Ok(day2::task1(&day2::CyclicGame::ROCK_PAPER_SCISSOR, input.lines())?.to_string())
+            },
+            |input| {
+                Ok(day2::task2(&day2::CyclicGame::ROCK_PAPER_SCISSOR, input.lines())?.to_string())
+            },
+        ],
+    },
+    Puzzle {
+        day: 3,
+        parts: [
+            |input| Ok(day3::task1(input.lines())?.to_string()),
+            |input| Ok(day3::task2(input.lines())?.to_string()),
+        ],
+    },
+];
+
+/// Looks up the puzzle of `day`.
+pub fn puzzle(day: u32) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|p| p.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry() {
+        assert!(PUZZLES.windows(2).all(|w| w[0].day < w[1].day));
+        assert_eq!(puzzle(2).map(|p| p.day), Some(2));
+        assert!(puzzle(26).is_none());
+
+        let day1 = puzzle(1).unwrap();
+        assert_eq!(day1.solve(1, "1\n2\n\n4").unwrap(), "4");
+        assert!(day1.solve(3, "").is_err());
+        assert_eq!(
+            puzzle(2)
+                .unwrap()
+                .solve(1, "A Y\nA")
+                .unwrap_err()
+                .root_cause()
+                .to_string(),
+            "line 2: expected 2 columns, found 1"
+        );
+        assert!(day1.input_path().ends_with("assets/day1.txt"));
+    }
+}
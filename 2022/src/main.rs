@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use advent_of_code_2022::{puzzle, PUZZLES};
+use anyhow::{Context, Result};
+use clap::Parser;
+
+#[cfg(feature = "dhat-heap")]
+aoc_profiling::global_allocator!();
+
+/// Solves the Advent of Code 2022 puzzles.
+#[derive(Parser, Debug)]
+#[command(version)]
+struct Args {
+    /// Day to solve.
+    day: u32,
+
+    /// Part to solve; both parts when left out.
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Puzzle input, defaults to `assets/dayN.txt` in the crate directory.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    tracing_subscriber::fmt::init();
+    let args = Args::parse();
+
+    let Some(puzzle) = puzzle(args.day) else {
+        let days = PUZZLES
+            .iter()
+            .map(|p| p.day.to_string())
+            .collect::<Vec<_>>();
+        anyhow::bail!(
+            "day {} is not solved, pick one of {}",
+            args.day,
+            days.join(", ")
+        );
+    };
+    let path = args.input.unwrap_or_else(|| puzzle.input_path());
+    let input = std::fs::read_to_string(&path)
+        .with_context(|| format!("unable to read {}", path.display()))?;
+
+    let parts = match args.part {
+        Some(part) => vec![part as usize],
+        None => vec![1, 2],
+    };
+    for part in parts {
+        #[cfg(feature = "dhat-heap")]
+        let _profiler = aoc_profiling::profile(&format!("day{}-part{}", puzzle.day, part));
+        println!("Task{} = {}", part, puzzle.solve(part, &input)?);
+    }
+    Ok(())
+}
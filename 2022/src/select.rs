@@ -0,0 +1,37 @@
+//! Selection of the largest values without sorting them all.
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// The `k` largest values, largest first.
+///
+/// The values are streamed through a min-heap holding the `k` largest seen so far, which takes
+/// `O(n log k)` time and `O(k)` memory.
+pub fn top_k<T: Ord>(values: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    if k == 0 {
+        return vec![];
+    }
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for value in values {
+        if heap.len() < k {
+            heap.push(Reverse(value));
+        } else if let Some(mut smallest) = heap.peek_mut() {
+            if value > smallest.0 {
+                *smallest = Reverse(value);
+            }
+        }
+    }
+    heap.into_sorted_vec().into_iter().map(|r| r.0).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn largest_first() {
+        assert_eq!(top_k([5, 1, 9, 3, 9, 7], 3), vec![9, 9, 7]);
+        assert_eq!(top_k([2, 1], 3), vec![2, 1]);
+        assert_eq!(top_k([2, 1], 0), Vec::<i32>::new());
+        assert_eq!(top_k(Vec::<i32>::new(), 2), vec![]);
+    }
+}
@@ -1,16 +1,15 @@
+//! Starting point for a new day.
+//!
+//! Copy this file to `src/dayN.rs`, declare the module in `lib.rs` and add it to
+//! [`PUZZLES`](crate::PUZZLES). This file itself is not part of the build.
+
 use std::str::Lines;
 
 use itertools::Itertools;
 #[allow(unused)]
 use tracing::{debug, error, info, instrument, warn};
 
-fn main() {
-    let input = std::fs::read_to_string(r"assets/dayX.txt").unwrap();
-    info!(task1=?task1(input.lines()));
-    info!(task2=?task2(input.lines()));
-}
-
-fn task1(input: Lines) -> usize {
+pub fn task1(input: Lines) -> usize {
     input
         .batching(|itt| {
             itt.map_while(|line| -> Option<usize> { line.parse().ok() })
@@ -20,7 +19,7 @@ fn task1(input: Lines) -> usize {
         .unwrap()
 }
 
-fn task2(input: Lines) -> usize {
+pub fn task2(input: Lines) -> usize {
     drop(input.collect_vec());
     0
 }
@@ -0,0 +1,35 @@
+//! Allocations of the puzzles, in a test binary of their own as it installs the dhat allocator.
+
+use advent_of_code_2022::puzzle;
+
+aoc_profiling::global_allocator!();
+
+/// Reads the input and measures solving `part` of `day`, holding the heap meter throughout.
+fn heap_usage(day: u32, part: usize) -> aoc_profiling::HeapUsage {
+    let heap = aoc_profiling::heap_meter();
+    let puzzle = puzzle(day).unwrap();
+    let input = std::fs::read_to_string(puzzle.input_path()).unwrap();
+    heap.measure(|| puzzle.solve(part, &input).unwrap()).1
+}
+
+#[test]
+fn day1_allocations() {
+    for part in 1..=2 {
+        let usage = heap_usage(1, part);
+        usage.assert_blocks_below(1_000);
+        usage.assert_max_bytes_below(128 * 1024);
+    }
+}
+
+#[test]
+fn day2_allocations() {
+    for part in 1..=2 {
+        heap_usage(2, part).assert_max_bytes_below(128 * 1024);
+    }
+}
+
+#[test]
+fn day3_allocations() {
+    heap_usage(3, 1).assert_blocks_below(4);
+    heap_usage(3, 2).assert_blocks_below(200);
+}
@@ -0,0 +1,281 @@
+//! A layer writing spans and events in the trace-event format of Chrome, as shown by
+//! `chrome://tracing` and <https://ui.perfetto.dev> without anything running besides the browser.
+
+use std::{
+    cell::Cell,
+    fmt,
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use serde_json::{json, Map, Value};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+/// The fields of a span or an event, as the arguments of a trace event.
+#[derive(Debug, Default)]
+struct Fields(Map<String, Value>);
+
+impl Visit for Fields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_owned(), format!("{:?}", value).into());
+    }
+}
+
+/// The JSON array of trace events, closed by [`FlushGuard`].
+struct Output<W> {
+    out: W,
+    events: usize,
+    finished: bool,
+    /// The first error writing, after which nothing more is written.
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Output<W> {
+    fn write(&mut self, event: &Value) {
+        if self.finished || self.error.is_some() {
+            return;
+        }
+        let separator = if self.events == 0 { "[\n" } else { ",\n" };
+        if let Err(e) = write!(self.out, "{}{}", separator, event) {
+            self.error = Some(e);
+        }
+        self.events += 1;
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        if !self.finished {
+            self.finished = true;
+            let open = if self.events == 0 { "[" } else { "" };
+            write!(self.out, "{}\n]\n", open)?;
+        }
+        self.out.flush()
+    }
+}
+
+/// Numbers the threads in the order they first trace something.
+fn thread_id() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    thread_local! {
+        static ID: Cell<u64> = const { Cell::new(0) };
+    }
+    ID.with(|id| {
+        if id.get() == 0 {
+            id.set(NEXT.fetch_add(1, Ordering::Relaxed));
+        }
+        id.get()
+    })
+}
+
+/// Writes a begin and an end event whenever a span is entered and exited, and an instant event
+/// for every event. The fields of spans and events become the arguments of the trace events.
+pub struct ChromeLayer<W> {
+    output: Arc<Mutex<Output<W>>>,
+    start: Instant,
+}
+
+/// Closes the trace when dropped. Spans entered afterwards aren't traced anymore.
+pub struct FlushGuard<W: Write> {
+    output: Arc<Mutex<Output<W>>>,
+}
+
+impl<W: Write> ChromeLayer<W> {
+    pub fn new(out: W) -> (Self, FlushGuard<W>) {
+        let output = Arc::new(Mutex::new(Output {
+            out,
+            events: 0,
+            finished: false,
+            error: None,
+        }));
+        let layer = Self {
+            output: output.clone(),
+            start: Instant::now(),
+        };
+        (layer, FlushGuard { output })
+    }
+
+    fn write(
+        &self,
+        phase: &str,
+        name: &str,
+        metadata: &Metadata,
+        args: Option<Map<String, Value>>,
+    ) {
+        let mut event = json!({
+            "ph": phase,
+            "name": name,
+            "cat": metadata.target(),
+            "ts": self.start.elapsed().as_nanos() as f64 / 1e3,
+            "pid": std::process::id(),
+            "tid": thread_id(),
+        });
+        if let Some(args) = args {
+            event["args"] = Value::Object(args);
+        }
+        if phase == "i" {
+            // instant events are drawn on the thread they happened on
+            event["s"] = "t".into();
+        }
+        if let Ok(mut output) = self.output.lock() {
+            output.write(&event);
+        }
+    }
+}
+
+impl<W: Write> FlushGuard<W> {
+    /// Closes the trace, reporting the first error writing it.
+    pub fn finish(&self) -> io::Result<()> {
+        match self.output.lock() {
+            Ok(mut output) => output.finish(),
+            Err(_) => Err(io::Error::other("the trace was poisoned")),
+        }
+    }
+}
+
+impl<W: Write> Drop for FlushGuard<W> {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish() {
+            eprintln!("unable to write the trace: {}", e);
+        }
+    }
+}
+
+impl<S, W> Layer<S> for ChromeLayer<W>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: Write + Send + 'static,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(fields);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(fields) = span.extensions_mut().get_mut::<Fields>() {
+                values.record(fields);
+            }
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let args = span
+                .extensions()
+                .get::<Fields>()
+                .map(|fields| fields.0.clone());
+            self.write("B", span.name(), span.metadata(), args);
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            self.write("E", span.name(), span.metadata(), None);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let name = match fields.0.remove("message") {
+            Some(Value::String(message)) => message,
+            _ => event.metadata().name().to_owned(),
+        };
+        self.write("i", &name, event.metadata(), Some(fields.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// A buffer shared between the layer and the test.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn trace_events() {
+        let buffer = Buffer::default();
+        let (layer, guard) = ChromeLayer::new(buffer.clone());
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::trace_span!("range_map_entry", entries = 3);
+            let _entered = span.enter();
+            tracing::debug!(location = 35, "found");
+        });
+        drop(guard);
+
+        let trace: Vec<Value> = serde_json::from_slice(&buffer.0.lock().unwrap()).unwrap();
+        let phases = trace
+            .iter()
+            .map(|e| (e["ph"].as_str().unwrap(), e["name"].as_str().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("B", "range_map_entry"),
+                ("i", "found"),
+                ("E", "range_map_entry")
+            ],
+            phases
+        );
+        assert_eq!(json!({ "entries": 3 }), trace[0]["args"]);
+        assert_eq!(json!({ "location": 35 }), trace[1]["args"]);
+        assert_eq!(trace[0]["tid"], trace[2]["tid"]);
+        assert!(trace[0]["ts"].as_f64() <= trace[2]["ts"].as_f64());
+    }
+
+    #[test]
+    fn empty_trace() {
+        let buffer = Buffer::default();
+        let (_layer, guard) = ChromeLayer::new(buffer.clone());
+        guard.finish().unwrap();
+        guard.finish().unwrap();
+        assert_eq!(b"[\n]\n", &buffer.0.lock().unwrap()[..]);
+    }
+}
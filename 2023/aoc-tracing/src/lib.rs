@@ -0,0 +1,71 @@
+//! The `tracing` subscriber of the part binaries, picked on their command line: the log alone,
+//! or the log along with the timings of every span, as a Chrome trace or streamed to Tracy.
+
+mod chrome;
+
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+use anyhow::{Context, Result};
+pub use chrome::{ChromeLayer, FlushGuard};
+use clap::Parser;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Trace {
+    /// Only the log, filtered by `RUST_LOG`.
+    Log,
+    /// Chrome trace-event JSON, to be opened in `chrome://tracing` or https://ui.perfetto.dev.
+    Chrome,
+    /// A stream to the Tracy profiler, needs the `tracy` feature.
+    Tracy,
+}
+
+#[derive(Debug, Parser)]
+pub struct TraceArgs {
+    /// Where the timings of the spans go.
+    #[arg(long, value_enum, default_value_t = Trace::Log)]
+    pub trace: Trace,
+    /// File receiving the Chrome trace.
+    #[arg(long, default_value = "trace.json")]
+    pub trace_file: PathBuf,
+}
+
+/// Keeps the trace open until dropped.
+pub struct TraceGuard {
+    _chrome: Option<FlushGuard<BufWriter<File>>>,
+}
+
+impl TraceArgs {
+    /// Installs the global subscriber. Chrome traces and Tracy get every span, whatever `RUST_LOG`
+    /// leaves of the log.
+    pub fn init(&self) -> Result<TraceGuard> {
+        let log = tracing_subscriber::fmt::layer().with_filter(EnvFilter::from_default_env());
+        let registry = tracing_subscriber::registry().with(log);
+        let mut guard = TraceGuard { _chrome: None };
+        match self.trace {
+            Trace::Log => registry.try_init()?,
+            Trace::Chrome => {
+                if let Some(dir) = self.trace_file.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                let file = File::create(&self.trace_file)
+                    .with_context(|| format!("unable to create {}", self.trace_file.display()))?;
+                let (layer, flush) = ChromeLayer::new(BufWriter::new(file));
+                guard._chrome = Some(flush);
+                registry.with(layer).try_init()?;
+            }
+            #[cfg(feature = "tracy")]
+            Trace::Tracy => registry.with(tracing_tracy::TracyLayer::new()).try_init()?,
+            #[cfg(not(feature = "tracy"))]
+            Trace::Tracy => {
+                anyhow::bail!("tracing to Tracy needs a build with the tracy feature")
+            }
+        }
+        Ok(guard)
+    }
+}
+
+/// Installs the subscriber picked by the arguments of the binary.
+pub fn init() -> Result<TraceGuard> {
+    TraceArgs::parse().init()
+}
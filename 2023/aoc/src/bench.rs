@@ -0,0 +1,464 @@
+//! Benchmarks of the registered days of every year, kept as a history of runs.
+//!
+//! Every stage of a day - parsing, and solving both parts - is timed on its own. Days that don't
+//! parse on their own only have their parts timed, parsing included. Slow stages are sampled fewer
+//! times, within a time budget per stage. A run is appended to the history as a line of JSON, and
+//! compared with the latest earlier run on the same machine.
+
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    hint::black_box,
+    io::Write,
+    path::Path,
+    process::Command,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use aoc_profiling::stats::format_ns;
+use serde::{Deserialize, Serialize};
+
+use crate::registry::Day;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        };
+        f.pad(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub year: u32,
+    pub day: u32,
+    pub stage: Stage,
+    pub samples: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+}
+
+impl Measurement {
+    fn new(day: &Day, stage: Stage, mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let ns = |d: Duration| d.as_nanos() as u64;
+        let total = samples.iter().sum::<Duration>();
+        Self {
+            year: day.year,
+            day: day.day,
+            stage,
+            samples: samples.len(),
+            min_ns: samples.first().copied().map_or(0, ns),
+            median_ns: samples.get(samples.len() / 2).copied().map_or(0, ns),
+            mean_ns: ns(total) / samples.len().max(1) as u64,
+        }
+    }
+}
+
+/// The machine a run was made on. Runs are only compared with runs of the same machine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Machine {
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+    /// Model name of the CPU, where known.
+    pub cpu: Option<String>,
+}
+
+impl Machine {
+    pub fn current() -> Self {
+        let cpu = fs::read_to_string("/proc/cpuinfo").ok().and_then(|info| {
+            info.lines()
+                .find_map(|l| l.strip_prefix("model name"))
+                .and_then(|l| l.split_once(':'))
+                .map(|(_, model)| model.trim().to_owned())
+        });
+        Self {
+            os: std::env::consts::OS.to_owned(),
+            arch: std::env::consts::ARCH.to_owned(),
+            cpus: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            cpu,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Git revision of the workspace, with uncommitted changes when `dirty`.
+    pub revision: Option<String>,
+    pub dirty: bool,
+    pub machine: Machine,
+    pub measurements: Vec<Measurement>,
+}
+
+impl Run {
+    /// A run of the current revision on this machine.
+    pub fn new(measurements: Vec<Measurement>) -> Self {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .output()
+                .ok()
+                .filter(|out| out.status.success())
+                .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_owned())
+        };
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            revision: git(&["rev-parse", "--short", "HEAD"]),
+            dirty: git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()),
+            machine: Machine::current(),
+            measurements,
+        }
+    }
+}
+
+/// Times `f` for up to `samples` runs after a run to warm up, stopping early once the runs took
+/// `budget` in all. A warm-up taking the whole budget is the only sample.
+fn sample<T>(
+    samples: usize,
+    budget: Duration,
+    mut f: impl FnMut() -> Result<T>,
+) -> Result<Vec<Duration>> {
+    let mut time = || -> Result<Duration> {
+        let start = Instant::now();
+        black_box(f()?);
+        Ok(start.elapsed())
+    };
+    let warm_up = time()?;
+    if warm_up >= budget {
+        return Ok(vec![warm_up]);
+    }
+    let mut durations = vec![];
+    let mut spent = warm_up;
+    while durations.len() < samples && spent < budget {
+        let duration = time()?;
+        spent += duration;
+        durations.push(duration);
+    }
+    Ok(durations)
+}
+
+/// Times every stage of `day` on `input`, spending up to `budget` on each. Parsing is timed with
+/// the parser of part 1, and the parts are timed solving input parsed up front.
+pub fn measure(
+    day: &Day,
+    input: &str,
+    samples: usize,
+    budget: Duration,
+) -> Result<Vec<Measurement>> {
+    let mut measurements = vec![];
+    if day.parses_separately() {
+        let durations = sample(samples, budget, || day.parse(1, input))?;
+        measurements.push(Measurement::new(day, Stage::Parse, durations));
+    }
+    for (part, stage) in [(1, Stage::Part1), (2, Stage::Part2)] {
+        let solve = day.parse(part, input)?;
+        let durations =
+            sample(samples, budget, &solve).with_context(|| format!("{} part {}", day, part))?;
+        measurements.push(Measurement::new(day, stage, durations));
+    }
+    Ok(measurements)
+}
+
+/// Reads the runs of the history, a line of JSON each. A missing history has no runs.
+pub fn load_history(path: &Path) -> Result<Vec<Run>> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let history =
+        fs::read_to_string(path).with_context(|| format!("unable to read {}", path.display()))?;
+    history
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| {
+            serde_json::from_str(l)
+                .with_context(|| format!("{}:{} is not a run", path.display(), i + 1))
+        })
+        .collect()
+}
+
+pub fn append_history(path: &Path, run: &Run) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("unable to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(run)?)?;
+    Ok(())
+}
+
+/// The latest run made on `machine`.
+pub fn previous_run<'a>(history: &'a [Run], machine: &Machine) -> Option<&'a Run> {
+    history.iter().rev().find(|run| run.machine == *machine)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub year: u32,
+    pub day: u32,
+    pub stage: Stage,
+    pub median_ns: u64,
+    pub previous_ns: Option<u64>,
+}
+
+impl Comparison {
+    /// Relative change of the median since the previous run, `0.1` being 10% slower.
+    pub fn change(&self) -> Option<f64> {
+        self.previous_ns
+            .filter(|&previous| previous > 0)
+            .map(|previous| self.median_ns as f64 / previous as f64 - 1.)
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+/// Compares the medians of `current` with those of the same stages of `previous`.
+pub fn compare(previous: Option<&Run>, current: &Run) -> Vec<Comparison> {
+    current
+        .measurements
+        .iter()
+        .map(|m| Comparison {
+            year: m.year,
+            day: m.day,
+            stage: m.stage,
+            median_ns: m.median_ns,
+            previous_ns: previous
+                .into_iter()
+                .flat_map(|run| &run.measurements)
+                .find(|p| (p.year, p.day, p.stage) == (m.year, m.day, m.stage))
+                .map(|p| p.median_ns),
+        })
+        .collect()
+}
+
+/// A table of the comparisons. Changes beyond `threshold` are marked, and coloured when `color`
+/// is set: regressions in red, improvements in green.
+pub fn report(comparisons: &[Comparison], threshold: f64, color: bool) -> String {
+    let mut table = format!(
+        "{:>4}  {:>3}  {:<5}  {:>10}  {:>10}  {:>8}\n",
+        "year", "day", "stage", "median", "previous", "change"
+    );
+    for c in comparisons {
+        let change = c
+            .change()
+            .map_or("-".to_owned(), |change| format!("{:+.1}%", change * 100.));
+        let row = format!(
+            "{:>4}  {:>3}  {:<5}  {:>10}  {:>10}  {:>8}",
+            c.year,
+            c.day,
+            c.stage,
+            format_ns(c.median_ns),
+            c.previous_ns.map_or("-".to_owned(), format_ns),
+            change
+        );
+        let improved = c.change().is_some_and(|change| change < -threshold);
+        let line = match (c.is_regression(threshold), improved, color) {
+            (true, _, true) => format!("\x1b[31m{}  regression\x1b[0m", row),
+            (true, _, false) => format!("{}  regression", row),
+            (_, true, true) => format!("\x1b[32m{}\x1b[0m", row),
+            _ => row,
+        };
+        table.push_str(&line);
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::Solvers;
+
+    fn run(timestamp: u64, cpus: usize, medians: &[(Stage, u64)]) -> Run {
+        Run {
+            timestamp,
+            revision: Some("abc1234".to_owned()),
+            dirty: false,
+            machine: Machine {
+                os: "linux".to_owned(),
+                arch: "x86_64".to_owned(),
+                cpus,
+                cpu: None,
+            },
+            measurements: medians
+                .iter()
+                .map(|&(stage, median_ns)| Measurement {
+                    year: 2023,
+                    day: 1,
+                    stage,
+                    samples: 10,
+                    min_ns: median_ns,
+                    median_ns,
+                    mean_ns: median_ns,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn measurement() {
+        let samples = [5, 1, 3, 2].map(Duration::from_nanos).to_vec();
+        let day = crate::registry::find(2023, 4).unwrap();
+        let m = Measurement::new(&day, Stage::Part2, samples);
+        assert_eq!((2023, 4), (m.year, m.day));
+        assert_eq!((4, 1, 3, 2), (m.samples, m.min_ns, m.median_ns, m.mean_ns));
+    }
+
+    #[test]
+    fn sample_within_budget() -> Result<()> {
+        let sleep = |ms| {
+            move || {
+                std::thread::sleep(Duration::from_millis(ms));
+                Ok(())
+            }
+        };
+        assert_eq!(5, sample(5, Duration::from_secs(10), sleep(1))?.len());
+        let durations = sample(100, Duration::from_millis(50), sleep(10))?;
+        assert!((1..5).contains(&durations.len()), "{:?}", durations);
+        // a slow warm-up is the only run
+        let durations = sample(100, Duration::from_millis(10), sleep(20))?;
+        assert_eq!(1, durations.len());
+        assert!(durations[0] >= Duration::from_millis(20));
+        Ok(())
+    }
+
+    #[test]
+    fn measure_day() -> Result<()> {
+        let day = Day {
+            year: 2023,
+            day: 7,
+            crate_name: "day_07",
+            solvers: Solvers::Staged([
+                |input| {
+                    assert_eq!(input, "input");
+                    Ok(Box::new(|| Ok("1".to_owned())))
+                },
+                |_| Ok(Box::new(|| anyhow::bail!("unsolved"))),
+            ]),
+        };
+        assert!(measure(&day, "input", 3, Duration::from_secs(10)).is_err());
+
+        let Solvers::Staged([part1, _]) = day.solvers else {
+            unreachable!()
+        };
+        let day = Day {
+            solvers: Solvers::Staged([part1, part1]),
+            ..day
+        };
+        let stages = measure(&day, "input", 3, Duration::from_secs(10))?
+            .into_iter()
+            .map(|m| (m.day, m.stage, m.samples))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (7, Stage::Parse, 3),
+                (7, Stage::Part1, 3),
+                (7, Stage::Part2, 3)
+            ],
+            stages
+        );
+
+        // the 2022 days parse as part of solving
+        let day = crate::registry::find(2022, 1).unwrap();
+        let stages = measure(&day, "1\n\n2", 3, Duration::from_secs(10))?
+            .into_iter()
+            .map(|m| (m.year, m.stage))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(2022, Stage::Part1), (2022, Stage::Part2)], stages);
+        Ok(())
+    }
+
+    #[test]
+    fn history() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("benches/history.jsonl");
+        assert!(load_history(&path)?.is_empty());
+
+        let first = run(1, 8, &[(Stage::Part1, 100)]);
+        let second = run(2, 4, &[(Stage::Part1, 200)]);
+        append_history(&path, &first)?;
+        append_history(&path, &second)?;
+        let history = load_history(&path)?;
+        assert_eq!(vec![first.clone(), second.clone()], history);
+        assert!(fs::read_to_string(&path)?.contains(r#""stage":"part1""#));
+
+        assert_eq!(Some(&first), previous_run(&history, &first.machine));
+        assert_eq!(Some(&second), previous_run(&history, &second.machine));
+        let other = Machine {
+            cpus: 2,
+            ..first.machine.clone()
+        };
+        assert_eq!(None, previous_run(&history, &other));
+
+        fs::write(&path, "{}\n")?;
+        assert!(load_history(&path).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn comparisons() {
+        let previous = run(1, 8, &[(Stage::Part1, 1000), (Stage::Part2, 2000)]);
+        let current = run(
+            2,
+            8,
+            &[
+                (Stage::Parse, 50),
+                (Stage::Part1, 1200),
+                (Stage::Part2, 1000),
+            ],
+        );
+        let comparisons = compare(Some(&previous), &current);
+        let changes = comparisons.iter().map(|c| c.change()).collect::<Vec<_>>();
+        assert_eq!(vec![None, Some(0.19999999999999996), Some(-0.5)], changes);
+        assert!(comparisons[1].is_regression(0.1));
+        assert!(!comparisons[1].is_regression(0.25));
+        assert!(!comparisons[2].is_regression(0.1));
+        assert!(compare(None, &current).iter().all(|c| c.change().is_none()));
+
+        let table = report(&comparisons, 0.1, false);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(
+            "year  day  stage      median    previous    change",
+            lines[0]
+        );
+        assert_eq!(
+            "2023    1  parse       50 ns           -         -",
+            lines[1]
+        );
+        assert_eq!(
+            "2023    1  part1     1200 ns     1000 ns    +20.0%  regression",
+            lines[2]
+        );
+        assert_eq!(
+            "2023    1  part2     1000 ns     2000 ns    -50.0%",
+            lines[3]
+        );
+        let colored = report(&comparisons, 0.1, true);
+        assert!(colored.contains("\x1b[31m2023    1  part1"));
+        assert!(colored.contains("\x1b[32m2023    1  part2"));
+    }
+}
@@ -0,0 +1,362 @@
+//! Creation of the crate for a new day.
+//!
+//! The crate is rendered from the files in `templates/`, where `{{project-name}}` and
+//! `{{crate_name}}` are replaced by the names of the crate, `{{example}}` by the example input of
+//! the puzzle, and `{{dependencies}}` by the dependencies of the chosen [`Template`].
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use toml_edit::{value, Array, Document, InlineTable};
+
+/// The kind of puzzle a new day starts out as, deciding the starter types of its parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Template {
+    /// A list of lines.
+    Lines,
+    /// A rectangular grid of characters.
+    Grid,
+    /// A graph given by a list of edges.
+    Graph,
+}
+
+impl Template {
+    fn parser(self) -> &'static str {
+        match self {
+            Template::Lines => include_str!("../templates/src/parser/lines.rs"),
+            Template::Grid => include_str!("../templates/src/parser/grid.rs"),
+            Template::Graph => include_str!("../templates/src/parser/graph.rs"),
+        }
+    }
+
+    fn dependencies(self) -> &'static [&'static str] {
+        match self {
+            Template::Lines => &[],
+            Template::Grid => &["glam"],
+            Template::Graph => &["petgraph"],
+        }
+    }
+}
+
+const FILES: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.tmpl")),
+    (
+        "benches/benchmarks.rs",
+        include_str!("../templates/benches/benchmarks.rs"),
+    ),
+    (
+        "src/bin/part1.rs",
+        include_str!("../templates/src/bin/part1.rs"),
+    ),
+    (
+        "src/bin/part2.rs",
+        include_str!("../templates/src/bin/part2.rs"),
+    ),
+    (
+        "src/custom_error.rs",
+        include_str!("../templates/src/custom_error.rs"),
+    ),
+    ("src/lib.rs", include_str!("../templates/src/lib.rs")),
+    ("src/part1.rs", include_str!("../templates/src/part1.rs")),
+    ("src/part2.rs", include_str!("../templates/src/part2.rs")),
+];
+
+#[derive(Debug, Clone)]
+pub struct NewDay {
+    pub year: u32,
+    pub day: u32,
+    pub template: Template,
+    /// Example input from the puzzle description, used by the tests of both parts.
+    pub example: Option<String>,
+}
+
+impl NewDay {
+    fn project_name(&self) -> String {
+        format!("day-{:02}", self.day)
+    }
+
+    fn crate_name(&self) -> String {
+        format!("day_{:02}", self.day)
+    }
+
+    fn render(&self, template: &str) -> String {
+        let dependencies = self
+            .template
+            .dependencies()
+            .iter()
+            .map(|dep| format!("{} = {{ workspace = true }}\n", dep))
+            .collect::<String>();
+        let example = self
+            .example
+            .as_deref()
+            .unwrap_or_default()
+            .trim_end()
+            .lines()
+            .map(|l| match l.trim_end() {
+                "" => String::new(),
+                l => format!("        {}", l),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        template
+            .replace("{{project-name}}", &self.project_name())
+            .replace("{{crate_name}}", &self.crate_name())
+            .replace("{{dependencies}}\n", &dependencies)
+            .replace("{{example}}", &example)
+    }
+}
+
+/// Creates the crate of a new day in the workspace of its year, found in `root/<year>`, and
+/// registers it in the workspace and with the runner of the workspace. Returns the directory of
+/// the new crate.
+pub fn create(root: &Path, new: &NewDay) -> Result<PathBuf> {
+    if !(1..=25).contains(&new.day) {
+        bail!("day {} is not in 1..=25", new.day);
+    }
+    if new.example.as_deref().unwrap_or_default().contains("\"#") {
+        bail!("the example contains '\"#', which would end its raw string literal");
+    }
+
+    let workspace = root.join(new.year.to_string());
+    let dir = workspace.join(new.project_name());
+    if dir.exists() {
+        bail!("{} already exists", dir.display());
+    }
+    let manifest = workspace.join("Cargo.toml");
+    let mut workspace_manifest = read_manifest(&manifest)?;
+    // the new crate takes all its dependencies from the workspace
+    if workspace_manifest
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(|d| d.as_table())
+        .is_none()
+    {
+        bail!(
+            "{} is not a workspace with shared dependencies",
+            manifest.display()
+        );
+    }
+
+    let files = FILES
+        .iter()
+        .copied()
+        .chain([("src/parser.rs", new.template.parser()), ("input.txt", "")]);
+    for (path, template) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, new.render(template))
+            .with_context(|| format!("unable to write {}", path.display()))?;
+    }
+
+    if add_member(&mut workspace_manifest, &new.project_name())? {
+        write_manifest(&manifest, &workspace_manifest)?;
+    }
+    register(&workspace, new)?;
+    Ok(dir)
+}
+
+fn read_manifest(path: &Path) -> Result<Document> {
+    fs::read_to_string(path)
+        .with_context(|| format!("unable to read {}", path.display()))?
+        .parse()
+        .with_context(|| format!("unable to parse {}", path.display()))
+}
+
+fn write_manifest(path: &Path, manifest: &Document) -> Result<()> {
+    fs::write(path, manifest.to_string())
+        .with_context(|| format!("unable to write {}", path.display()))
+}
+
+/// Adds `name` to the members of the workspace, unless a member pattern already covers it.
+/// Returns whether the manifest was changed.
+fn add_member(manifest: &mut Document, name: &str) -> Result<bool> {
+    let members = manifest["workspace"]
+        .as_table_mut()
+        .context("no workspace table")?
+        .entry("members")
+        .or_insert(value(Array::new()))
+        .as_array_mut()
+        .context("workspace members is not an array")?;
+    let covered = members
+        .iter()
+        .filter_map(|m| m.as_str())
+        .any(|m| match m.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => m == name,
+        });
+    if !covered {
+        members.push(name);
+    }
+    Ok(!covered)
+}
+
+/// Adds the day to the `aoc` crate of the workspace, as a dependency and in its registry.
+/// Workspaces without the `aoc` crate are left as they are.
+fn register(workspace: &Path, new: &NewDay) -> Result<()> {
+    let aoc = workspace.join("aoc");
+    let registry = aoc.join("src/registry.rs");
+    if !registry.exists() {
+        return Ok(());
+    }
+
+    let manifest_path = aoc.join("Cargo.toml");
+    let mut manifest = read_manifest(&manifest_path)?;
+    let mut dependency = InlineTable::new();
+    dependency.insert("path", format!("../{}", new.project_name()).into());
+    manifest["dependencies"][&new.project_name()] = value(dependency);
+    write_manifest(&manifest_path, &manifest)?;
+
+    let source = fs::read_to_string(&registry)
+        .with_context(|| format!("unable to read {}", registry.display()))?;
+    let entry = format!("    day!({}, {}, {}),", new.year, new.day, new.crate_name());
+    let mut lines = source.lines().collect::<Vec<_>>();
+    let registered = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| {
+            let mut args = l.trim().strip_prefix("day!(")?.split(',');
+            let mut number = || args.next()?.trim().parse::<u32>().ok();
+            Some((i, (number()?, number()?)))
+        })
+        .collect::<Vec<_>>();
+    let Some(&(last, _)) = registered.last() else {
+        bail!("no days registered in {}", registry.display());
+    };
+    let at = registered
+        .iter()
+        .find(|&&(_, year_day)| year_day > (new.year, new.day))
+        .map_or(last + 1, |&(i, _)| i);
+    lines.insert(at, &entry);
+    fs::write(&registry, lines.join("\n") + "\n")
+        .with_context(|| format!("unable to write {}", registry.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const REGISTRY: &str = indoc! {"
+        pub const DAYS: &[Day] = &[
+            day!(2023, 1, day_01),
+            day!(2023, 3, day_03),
+        ];
+    "};
+
+    fn workspace(members: &str) -> Result<tempfile::TempDir> {
+        let root = tempfile::tempdir()?;
+        let workspace = root.path().join("2023");
+        fs::create_dir_all(workspace.join("aoc/src"))?;
+        fs::write(
+            workspace.join("Cargo.toml"),
+            format!(
+                "[workspace]\nmembers = {}\n\n[workspace.dependencies]\nnom = \"7\"\n",
+                members
+            ),
+        )?;
+        fs::write(
+            workspace.join("aoc/Cargo.toml"),
+            "[dependencies]\nday-01 = { path = \"../day-01\" }\n",
+        )?;
+        fs::write(workspace.join("aoc/src/registry.rs"), REGISTRY)?;
+        Ok(root)
+    }
+
+    fn new_day(day: u32, template: Template, example: Option<&str>) -> NewDay {
+        NewDay {
+            year: 2023,
+            day,
+            template,
+            example: example.map(ToOwned::to_owned),
+        }
+    }
+
+    #[test]
+    fn create_day() -> Result<()> {
+        let root = workspace(r#"["aoc"]"#)?;
+        let new = new_day(2, Template::Grid, Some("#.#\n\n..#\n"));
+        let dir = create(root.path(), &new)?;
+        let workspace = root.path().join("2023");
+        assert_eq!(dir, workspace.join("day-02"));
+
+        let cargo = fs::read_to_string(dir.join("Cargo.toml"))?;
+        assert!(cargo.contains("name = \"day-02\""));
+        assert!(cargo.contains("glam = { workspace = true }\n"));
+        assert!(!cargo.contains("{{"));
+        let part1 = fs::read_to_string(dir.join("src/part1.rs"))?;
+        assert!(part1.contains("        #.#\n\n        ..#\n        \"#}"));
+        assert!(part1.contains("todo!(\"day-02 - part 1\")"));
+        let bin = fs::read_to_string(dir.join("src/bin/part2.rs"))?;
+        assert!(bin.contains("use day_02::part2::process;"));
+        let parser = fs::read_to_string(dir.join("src/parser.rs"))?;
+        assert!(parser.contains("pub struct Grid"));
+        assert_eq!(fs::read_to_string(dir.join("input.txt"))?, "");
+
+        let manifest = fs::read_to_string(workspace.join("Cargo.toml"))?;
+        assert!(manifest.contains(r#"members = ["aoc", "day-02"]"#));
+        let aoc = fs::read_to_string(workspace.join("aoc/Cargo.toml"))?;
+        assert!(aoc.contains(r#"day-02 = { path = "../day-02" }"#));
+        assert_eq!(
+            fs::read_to_string(workspace.join("aoc/src/registry.rs"))?,
+            indoc! {"
+                pub const DAYS: &[Day] = &[
+                    day!(2023, 1, day_01),
+                    day!(2023, 2, day_02),
+                    day!(2023, 3, day_03),
+                ];
+            "}
+        );
+
+        assert!(create(root.path(), &new).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn member_patterns() -> Result<()> {
+        let root = workspace(r#"["aoc", "day-*"]"#)?;
+        create(root.path(), &new_day(7, Template::Lines, None))?;
+        let manifest = fs::read_to_string(root.path().join("2023/Cargo.toml"))?;
+        assert!(manifest.contains(r#"members = ["aoc", "day-*"]"#));
+        let registry = fs::read_to_string(root.path().join("2023/aoc/src/registry.rs"))?;
+        assert!(registry.contains("    day!(2023, 3, day_03),\n    day!(2023, 7, day_07),\n];"));
+        Ok(())
+    }
+
+    #[test]
+    fn other_year() -> Result<()> {
+        let root = workspace(r#"["aoc"]"#)?;
+        fs::rename(root.path().join("2023"), root.path().join("2024"))?;
+        let new = NewDay {
+            year: 2024,
+            ..new_day(1, Template::Lines, None)
+        };
+        create(root.path(), &new)?;
+        let registry = fs::read_to_string(root.path().join("2024/aoc/src/registry.rs"))?;
+        assert!(registry.contains("    day!(2023, 3, day_03),\n    day!(2024, 1, day_01),\n];"));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_days() -> Result<()> {
+        let root = workspace(r#"["aoc"]"#)?;
+        assert!(create(root.path(), &new_day(26, Template::Lines, None)).is_err());
+        assert!(create(root.path(), &new_day(4, Template::Graph, Some("\"#"))).is_err());
+        let other_year = NewDay {
+            year: 2022,
+            ..new_day(4, Template::Graph, None)
+        };
+        assert!(create(root.path(), &other_year).is_err());
+
+        // a package with an empty workspace table, like 2022
+        fs::write(
+            root.path().join("2023/Cargo.toml"),
+            "[workspace]\nmembers = []\n\n[package]\nname = \"aoc\"\n",
+        )?;
+        assert!(create(root.path(), &new_day(4, Template::Lines, None)).is_err());
+        assert!(!root.path().join("2023/day-04").exists());
+        Ok(())
+    }
+}
@@ -0,0 +1,6 @@
+//! Tooling for the Advent of Code workspace: creating new days, and running, benchmarking and
+//! measuring the heap usage of the registered ones.
+
+pub mod bench;
+pub mod generate;
+pub mod registry;
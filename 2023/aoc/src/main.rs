@@ -0,0 +1,224 @@
+use std::{io::IsTerminal, path::PathBuf, time::Duration};
+
+use anyhow::{bail, Context, Result};
+use aoc::{
+    bench::{self, Run},
+    generate::{self, NewDay, Template},
+    registry::{self, Day},
+};
+use aoc_profiling::stats::{self, CountingAlloc, PartUsage};
+use clap::{Parser, Subcommand};
+
+aoc_profiling::counting_allocator!();
+
+#[derive(Parser, Debug)]
+#[command(about = "Advent of Code tooling")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Creates the crate of a new day, and registers it.
+    New {
+        #[arg(long)]
+        year: u32,
+        #[arg(long)]
+        day: u32,
+        /// Kind of puzzle, deciding the starter types of the parser.
+        #[arg(long, value_enum, default_value_t = Template::Lines)]
+        template: Template,
+        /// File holding the example input of the puzzle description.
+        #[arg(long)]
+        example: Option<PathBuf>,
+        /// Directory holding the workspace of every year.
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/../.."))]
+        root: PathBuf,
+    },
+    /// Solves a registered day.
+    Run {
+        /// Day to solve; every registered day when left out.
+        day: Option<u32>,
+        /// Year of the days; the latest year when a day is given, and every year otherwise.
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Part to solve; both parts when left out.
+        #[arg(short, long)]
+        part: Option<usize>,
+        /// Puzzle input, defaults to the `input.txt` of the day.
+        #[arg(short, long, requires = "day")]
+        input: Option<PathBuf>,
+        /// Report the wall time, peak heap and number of allocations of every part.
+        #[arg(long)]
+        stats: bool,
+    },
+    /// Times every stage of the registered days, and compares the times with the previous run.
+    Bench {
+        /// Days to time; every registered day when left out.
+        days: Vec<u32>,
+        /// Year of the days; the latest year when days are given, and every year otherwise.
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Number of timed runs of every stage.
+        #[arg(short, long, default_value_t = 20)]
+        samples: usize,
+        /// Seconds to spend timing a stage, beyond which it gets no more runs than the first.
+        #[arg(short, long, default_value_t = 10.)]
+        budget: f64,
+        /// File keeping the runs, a line of JSON each.
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/../benches/history.jsonl"))]
+        history: PathBuf,
+        /// Slowdown reported as a regression, in percent.
+        #[arg(long, default_value_t = 10.)]
+        threshold: f64,
+        /// Don't add this run to the history.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// The registered days of `year` numbered `days`. Every day of `year` when `days` is empty, where
+/// `year` defaults to the latest year when days are given, and to every year otherwise.
+fn select_days(year: Option<u32>, days: &[u32]) -> Result<Vec<Day>> {
+    let registered = registry::all();
+    let year = match year {
+        None if !days.is_empty() => registered.iter().map(|d| d.year).max(),
+        year => year,
+    };
+    let in_year = |d: &Day| year.is_none_or(|year| d.year == year);
+    if days.is_empty() {
+        let selected = registered.into_iter().filter(in_year).collect::<Vec<_>>();
+        if let (Some(year), true) = (year, selected.is_empty()) {
+            bail!("no day of {} is registered", year);
+        }
+        return Ok(selected);
+    }
+    days.iter()
+        .map(|&day| {
+            registered
+                .iter()
+                .find(|d| in_year(d) && d.day == day)
+                .copied()
+                .with_context(|| {
+                    let days = registered
+                        .iter()
+                        .filter(|d| in_year(d))
+                        .map(|d| d.day.to_string())
+                        .collect::<Vec<_>>();
+                    format!(
+                        "day {} is not registered, pick one of {}",
+                        day,
+                        days.join(", ")
+                    )
+                })
+        })
+        .collect()
+}
+
+fn read_input(day: &Day) -> Result<String> {
+    let path = day.input_path();
+    std::fs::read_to_string(&path).with_context(|| format!("unable to read {}", path.display()))
+}
+
+fn main() -> Result<()> {
+    tracing_subscriber::fmt::init();
+
+    match Cli::parse().command {
+        Command::New {
+            year,
+            day,
+            template,
+            example,
+            root,
+        } => {
+            let example = example
+                .map(|path| {
+                    std::fs::read_to_string(&path)
+                        .with_context(|| format!("unable to read {}", path.display()))
+                })
+                .transpose()?;
+            let new = NewDay {
+                year,
+                day,
+                template,
+                example,
+            };
+            let dir = generate::create(&root, &new)?;
+            println!("created {}", dir.display());
+        }
+        Command::Run {
+            day,
+            year,
+            part,
+            input,
+            stats,
+        } => {
+            if stats {
+                if cfg!(debug_assertions) {
+                    eprintln!(
+                        "warning: measuring a debug build, use --release for meaningful times"
+                    );
+                }
+                CountingAlloc::enable();
+            }
+            let days = select_days(year, &day.into_iter().collect::<Vec<_>>())?;
+            let parts = part.map_or(vec![1, 2], |part| vec![part]);
+            let mut usages = vec![];
+            for day in &days {
+                let input = match &input {
+                    Some(path) => std::fs::read_to_string(path)
+                        .with_context(|| format!("unable to read {}", path.display()))?,
+                    None => read_input(day)?,
+                };
+                for &part in &parts {
+                    let (answer, usage) = stats::measure(|| day.solve(part, &input));
+                    let answer = answer?;
+                    if days.len() > 1 {
+                        println!("{} part {}: {}", day, part, answer);
+                    } else {
+                        println!("part {}: {}", part, answer);
+                    }
+                    usages.push(PartUsage {
+                        year: day.year,
+                        day: day.day,
+                        part,
+                        usage,
+                    });
+                }
+            }
+            if stats {
+                print!("\n{}", stats::report(&usages));
+            }
+        }
+        Command::Bench {
+            days,
+            year,
+            samples,
+            budget,
+            history,
+            threshold,
+            dry_run,
+        } => {
+            if cfg!(debug_assertions) {
+                eprintln!("warning: timing a debug build, use --release for meaningful times");
+            }
+            let budget = Duration::try_from_secs_f64(budget).context("invalid budget")?;
+            let mut measurements = vec![];
+            for day in select_days(year, &days)? {
+                let input = read_input(&day)?;
+                measurements.extend(bench::measure(&day, &input, samples, budget)?);
+            }
+
+            let run = Run::new(measurements);
+            let previous = bench::load_history(&history)?;
+            let comparisons = bench::compare(bench::previous_run(&previous, &run.machine), &run);
+            let color = std::io::stdout().is_terminal();
+            print!("{}", bench::report(&comparisons, threshold / 100., color));
+            if !dry_run {
+                bench::append_history(&history, &run)?;
+            }
+        }
+    }
+    Ok(())
+}
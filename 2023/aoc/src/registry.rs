@@ -0,0 +1,190 @@
+//! The solved days of every year.
+//!
+//! The days of this workspace are listed in [`DAYS`], where `aoc new` registers new days by adding
+//! a [`day!`] line after the last one. The puzzles of 2021 and 2022 are registered by their own
+//! libraries.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+
+/// Solves a part from its parsed puzzle input, returning the answer.
+pub type Solve<'a> = Box<dyn Fn() -> Result<String> + 'a>;
+
+/// Parses the puzzle input of a part, returning the solver of the parsed input, so parsing and
+/// solving can be timed on their own.
+pub type Part = for<'a> fn(&'a str) -> Result<Solve<'a>>;
+
+/// How the parts of a day are solved.
+#[derive(Clone, Copy)]
+pub enum Solvers {
+    /// Parsing and solving are stages of their own.
+    Staged([Part; 2]),
+    /// A puzzle of the 2021 library, parsing and solving every part in one go.
+    Puzzle2021(&'static advent_of_code_2021::Puzzle),
+    /// A puzzle of the 2022 library, parsing and solving every part in one go.
+    Puzzle2022(&'static advent_of_code_2022::Puzzle),
+}
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub crate_name: &'static str,
+    pub solvers: Solvers,
+}
+
+impl Day {
+    /// Solves `part`, counting from 1.
+    pub fn solve(&self, part: usize, input: &str) -> Result<String> {
+        self.parse(part, input)?().with_context(|| format!("{} part {}", self, part))
+    }
+
+    /// Parses the input of `part`, counting from 1. Days that don't parse on their own only keep
+    /// the input, and parse it when solving.
+    pub fn parse<'a>(&self, part: usize, input: &'a str) -> Result<Solve<'a>> {
+        if !(1..=2).contains(&part) {
+            bail!("{} has no part {}", self, part);
+        }
+        match self.solvers {
+            Solvers::Staged(parts) => {
+                parts[part - 1](input).with_context(|| format!("{} part {} parse", self, part))
+            }
+            Solvers::Puzzle2021(puzzle) => Ok(Box::new(move || puzzle.solve(part, input))),
+            Solvers::Puzzle2022(puzzle) => Ok(Box::new(move || puzzle.solve(part, input))),
+        }
+    }
+
+    /// Whether parsing is a stage of its own.
+    pub fn parses_separately(&self) -> bool {
+        matches!(self.solvers, Solvers::Staged(_))
+    }
+
+    /// The puzzle input of the day, `input.txt` in its crate for the days of this workspace.
+    pub fn input_path(&self) -> PathBuf {
+        match self.solvers {
+            Solvers::Staged(_) => {
+                let dir = self.crate_name.replace('_', "-");
+                [env!("CARGO_MANIFEST_DIR"), "..", &dir, "input.txt"]
+                    .iter()
+                    .collect()
+            }
+            Solvers::Puzzle2021(puzzle) => puzzle.input_path(),
+            Solvers::Puzzle2022(puzzle) => puzzle.input_path(),
+        }
+    }
+}
+
+impl std::fmt::Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/// A part solved by `parse` and `solve` of the `$part` module of crate `$name`. `solve` is passed
+/// the parsed input, followed by `$arg`s.
+macro_rules! part {
+    ($name:ident::$part:ident $(, $arg:expr)*) => {
+        |input| {
+            let parsed = $name::$part::parse(input)?;
+            Ok(Box::new(move || {
+                Ok($name::$part::solve(&parsed $(, $arg)*)?.to_string())
+            }))
+        }
+    };
+}
+
+/// Registers day `$day` of `$year`, whose parts are solved by the `part1` and `part2` modules of
+/// its crate.
+macro_rules! day {
+    ($year:literal, $day:literal, $name:ident) => {
+        Day {
+            year: $year,
+            day: $day,
+            crate_name: stringify!($name),
+            solvers: Solvers::Staged([part!($name::part1), part!($name::part2)]),
+        }
+    };
+}
+
+/// The days of this workspace, ordered by year and day.
+pub const DAYS: &[Day] = &[
+    day!(2023, 1, day_01),
+    Day {
+        year: 2023,
+        day: 2,
+        crate_name: "day_02",
+        solvers: Solvers::Staged([
+            part!(day_02::part1, day_02::CubeCollection::new(12, 13, 14)),
+            part!(day_02::part2),
+        ]),
+    },
+    day!(2023, 3, day_03),
+    day!(2023, 4, day_04),
+    day!(2023, 5, day_05),
+    day!(2023, 6, day_06),
+];
+
+/// Every registered day of every year, ordered by year and day.
+pub fn all() -> Vec<Day> {
+    let puzzles_2021 = advent_of_code_2021::PUZZLES.iter().map(|puzzle| Day {
+        year: 2021,
+        day: puzzle.day,
+        crate_name: "advent_of_code_2021",
+        solvers: Solvers::Puzzle2021(puzzle),
+    });
+    let puzzles_2022 = advent_of_code_2022::PUZZLES.iter().map(|puzzle| Day {
+        year: 2022,
+        day: puzzle.day,
+        crate_name: "advent_of_code_2022",
+        solvers: Solvers::Puzzle2022(puzzle),
+    });
+    puzzles_2021
+        .chain(puzzles_2022)
+        .chain(DAYS.iter().copied())
+        .collect()
+}
+
+/// Looks up `day` of `year`.
+pub fn find(year: u32, day: u32) -> Option<Day> {
+    all().into_iter().find(|d| d.year == year && d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_days() -> Result<()> {
+        assert!(DAYS
+            .windows(2)
+            .all(|w| (w[0].year, w[0].day) < (w[1].year, w[1].day)));
+        let days = all();
+        assert!(days
+            .windows(2)
+            .all(|w| (w[0].year, w[0].day) < (w[1].year, w[1].day)));
+
+        let day = find(2023, 1).unwrap();
+        assert!(day.parses_separately());
+        assert!(day.input_path().ends_with("day-01/input.txt"));
+        assert!(day.solve(3, "").is_err());
+        assert_eq!("50", day.parse(1, "1abc2\npqr3stu8vwx")?()?);
+        assert!(find(2023, 26).is_none());
+
+        let day = find(2022, 1).unwrap();
+        assert!(!day.parses_separately());
+        assert!(day.input_path().ends_with("assets/day1.txt"));
+        assert_eq!("4", day.solve(1, "1\n2\n\n4")?);
+        assert_eq!(
+            "2022 day 1 part 2",
+            day.solve(2, "x").unwrap_err().to_string()
+        );
+
+        let day = find(2021, 1).unwrap();
+        assert!(!day.parses_separately());
+        assert!(day.input_path().ends_with("assets/day1.csv"));
+        assert_eq!("1", day.solve(1, "1\n2")?);
+        assert!(find(2021, 18).is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use {{crate_name}}::*;
+use divan::{black_box, Bencher};
+
+const INPUT: &str = include_str!("../input.txt");
+
+fn main() {
+    // Run registered benchmarks.
+    divan::main();
+}
+
+#[divan::bench]
+fn part1_parse() {
+    part1::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part1_solve(bencher: Bencher) {
+    let input = part1::parse(INPUT).unwrap();
+    bencher.bench(|| part1::solve(black_box(&input)).unwrap());
+}
+
+#[divan::bench]
+fn part2_parse() {
+    part2::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part2_solve(bencher: Bencher) {
+    let input = part2::parse(INPUT).unwrap();
+    bencher.bench(|| part2::solve(black_box(&input)).unwrap());
+}
+
+/// The same stages on generated inputs of a growing size.
+mod scaling {
+    use super::*;
+
+    const SIZES: &[usize] = &[10, 100, 1_000];
+
+    /// The example input of the puzzle, repeated `size` times. Replace this with a generator
+    /// that keeps to the rules of the puzzle, where repeating the example doesn't.
+    fn generate(size: usize) -> String {
+        indoc::indoc! {r#"
+{{example}}
+        "#}
+        .repeat(size)
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn part1_parse(bencher: Bencher, size: usize) {
+        let input = generate(size);
+        bencher.bench(|| part1::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn part1_solve(bencher: Bencher, size: usize) {
+        let generated = generate(size);
+        let input = part1::parse(&generated).unwrap();
+        bencher.bench(|| part1::solve(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn part2_parse(bencher: Bencher, size: usize) {
+        let input = generate(size);
+        bencher.bench(|| part2::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn part2_solve(bencher: Bencher, size: usize) {
+        let generated = generate(size);
+        let input = part2::parse(&generated).unwrap();
+        bencher.bench(|| part2::solve(black_box(&input)).unwrap());
+    }
+}
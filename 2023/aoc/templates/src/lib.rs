@@ -3,4 +3,4 @@ pub mod custom_error;
 pub mod part1;
 pub mod part2;
 
-mod parser;
+pub mod parser;
@@ -0,0 +1,22 @@
+use nom::{
+    bytes::complete::tag, character::complete::*, combinator::*, multi::*, sequence::*, IResult,
+};
+use petgraph::graphmap::UnGraphMap;
+use tracing::*;
+
+/// Nodes named by the input, connected by the edges listed in it.
+pub type Graph<'a> = UnGraphMap<&'a str, ()>;
+
+/// An edge written as `a-b`.
+fn edge(input: &str) -> IResult<&str, (&str, &str)> {
+    separated_pair(alphanumeric1, tag("-"), alphanumeric1)(input)
+}
+
+/// The parsed puzzle input.
+pub type Input<'a> = Graph<'a>;
+
+pub fn parse(input: &str) -> IResult<&str, Input<'_>> {
+    map(separated_list1(line_ending, edge), |edges| {
+        Graph::from_edges(edges)
+    })(input)
+}
@@ -0,0 +1,47 @@
+use glam::IVec2;
+use nom::{
+    bytes::complete::tag, character::complete::*, combinator::*, multi::*, sequence::*, IResult,
+};
+use tracing::*;
+
+/// A rectangular grid of characters, stored row by row.
+#[derive(Debug, PartialEq)]
+pub struct Grid {
+    pub size: IVec2,
+    pub cells: Vec<char>,
+}
+
+impl Grid {
+    pub fn get(&self, pos: IVec2) -> Option<char> {
+        self.contains(pos)
+            .then(|| self.cells[(pos.y * self.size.x + pos.x) as usize])
+    }
+
+    pub fn contains(&self, pos: IVec2) -> bool {
+        pos.cmpge(IVec2::ZERO).all() && pos.cmplt(self.size).all()
+    }
+
+    /// The positions next to `pos` horizontally and vertically, that are inside the grid.
+    pub fn neighbours(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+            .into_iter()
+            .map(move |d| pos + d)
+            .filter(|&p| self.contains(p))
+    }
+}
+
+/// The parsed puzzle input.
+pub type Input<'a> = Grid;
+
+pub fn parse(input: &str) -> IResult<&str, Input<'_>> {
+    map_opt(
+        separated_list1(line_ending, many1(none_of("\r\n"))),
+        |rows: Vec<Vec<char>>| {
+            let width = rows[0].len();
+            rows.iter().all(|row| row.len() == width).then(|| Grid {
+                size: IVec2::new(width as i32, rows.len() as i32),
+                cells: rows.concat(),
+            })
+        },
+    )(input)
+}
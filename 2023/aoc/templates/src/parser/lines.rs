@@ -0,0 +1,19 @@
+use nom::{
+    bytes::complete::tag, character::complete::*, combinator::*, multi::*, sequence::*, IResult,
+};
+use tracing::*;
+
+/// A single line of the puzzle input.
+#[derive(Debug, PartialEq)]
+pub struct Line<'a>(pub &'a str);
+
+fn line(input: &str) -> IResult<&str, Line<'_>> {
+    map(not_line_ending, Line)(input)
+}
+
+/// The parsed puzzle input.
+pub type Input<'a> = Vec<Line<'a>>;
+
+pub fn parse(input: &str) -> IResult<&str, Input<'_>> {
+    separated_list1(line_ending, line)(input)
+}
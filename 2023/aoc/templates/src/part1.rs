@@ -0,0 +1,41 @@
+use crate::{
+    custom_error::AocError,
+    parser::{self, Input},
+};
+use tracing::*;
+
+pub fn process(input: &str) -> Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+pub fn parse(input: &str) -> Result<Input<'_>, AocError> {
+    let (_, input) = parser::parse(input).map_err(|e| AocError::ParserError(e.to_string()))?;
+    Ok(input)
+}
+
+pub fn solve(input: &Input) -> Result<u32, AocError> {
+    todo!("{{project-name}} - part 1");
+
+    Ok(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use rstest::*;
+    use test_log::test;
+
+    #[fixture]
+    fn example() -> &'static str {
+        indoc::indoc! {r#"
+{{example}}
+        "#}
+    }
+
+    #[test(rstest)]
+    fn test_process(example: &str) -> Result<()> {
+        assert_eq!(0, process(example)?);
+        Ok(())
+    }
+}
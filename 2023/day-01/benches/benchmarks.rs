@@ -1,4 +1,7 @@
 use day_01::*;
+use divan::{black_box, Bencher};
+
+const INPUT: &str = include_str!("../input.txt");
 
 fn main() {
     // Run registered benchmarks.
@@ -6,11 +9,74 @@ fn main() {
 }
 
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+fn part1_parse() {
+    part1::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part1_solve(bencher: Bencher) {
+    let lines = part1::parse(INPUT).unwrap();
+    bencher.bench(|| part1::solve(black_box(&lines)).unwrap());
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+fn part2_parse() {
+    part2::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part2_solve(bencher: Bencher) {
+    let lines = part2::parse(INPUT).unwrap();
+    bencher.bench(|| part2::solve(black_box(&lines)).unwrap());
+}
+
+/// The same stages on generated documents of a growing number of lines.
+mod scaling {
+    use super::*;
+
+    const LINES: &[usize] = &[100, 1_000, 10_000];
+
+    /// A calibration document of `lines` lines, each with spelled out digits and digits.
+    fn generate(lines: usize) -> String {
+        const WORDS: [&str; 9] = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        (0..lines)
+            .map(|i| {
+                format!(
+                    "x{}{}k{}pq{}\n",
+                    i % 9 + 1,
+                    WORDS[i * 7 % 9],
+                    i * 5 % 9 + 1,
+                    WORDS[i % 9]
+                )
+            })
+            .collect()
+    }
+
+    #[divan::bench(args = LINES)]
+    fn part1_parse(bencher: Bencher, lines: usize) {
+        let input = generate(lines);
+        bencher.bench(|| part1::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = LINES)]
+    fn part1_solve(bencher: Bencher, lines: usize) {
+        let input = generate(lines);
+        let lines = part1::parse(&input).unwrap();
+        bencher.bench(|| part1::solve(black_box(&lines)).unwrap());
+    }
+
+    #[divan::bench(args = LINES)]
+    fn part2_parse(bencher: Bencher, lines: usize) {
+        let input = generate(lines);
+        bencher.bench(|| part2::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = LINES)]
+    fn part2_solve(bencher: Bencher, lines: usize) {
+        let input = generate(lines);
+        let lines = part2::parse(&input).unwrap();
+        bencher.bench(|| part2::solve(black_box(&lines)).unwrap());
+    }
 }
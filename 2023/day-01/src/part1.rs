@@ -4,9 +4,20 @@ use tracing::{debug, info};
 
 #[tracing::instrument(level = "trace", skip(input))]
 pub fn process(input: &str) -> Result<u32, AocError> {
-    let calibration_value = input
-        .lines()
-        .map(process_line)
+    solve(&parse(input)?)
+}
+
+/// Splits the calibration document into its lines, finding the digits is left to [`solve`].
+#[tracing::instrument(level = "trace", skip(input))]
+pub fn parse(input: &str) -> Result<Vec<&str>, AocError> {
+    Ok(input.lines().collect())
+}
+
+#[tracing::instrument(level = "trace", skip(lines))]
+pub fn solve(lines: &[&str]) -> Result<u32, AocError> {
+    let calibration_value = lines
+        .iter()
+        .map(|line| process_line(line))
         .process_results(|iter| iter.sum())?;
     info!(calibration_value);
     Ok(calibration_value)
@@ -18,7 +29,7 @@ fn process_line(line: &str) -> Result<u32, AocError> {
     let first = digit_iterator
         .next()
         .ok_or(AocError::invalid_input(line, "expected at least 1 digit"))?;
-    let last = digit_iterator.last().unwrap_or(first);
+    let last = digit_iterator.next_back().unwrap_or(first);
 
     let value = first * 10 + last;
     debug!(value);
@@ -3,7 +3,18 @@ use tracing::{debug, info, trace};
 
 #[tracing::instrument(level = "trace", skip(input))]
 pub fn process(input: &str) -> anyhow::Result<u32, AocError> {
-    let calibration_value = input.lines().map(process_line).sum();
+    solve(&parse(input)?)
+}
+
+/// Splits the calibration document into its lines, finding the digits is left to [`solve`].
+#[tracing::instrument(level = "trace", skip(input))]
+pub fn parse(input: &str) -> Result<Vec<&str>, AocError> {
+    Ok(input.lines().collect())
+}
+
+#[tracing::instrument(level = "trace", skip(lines))]
+pub fn solve(lines: &[&str]) -> Result<u32, AocError> {
+    let calibration_value = lines.iter().map(|line| process_line(line)).sum();
     info!(calibration_value);
     Ok(calibration_value)
 }
@@ -40,7 +51,7 @@ fn process_line(line: &str) -> u32 {
             })
     });
     if let Some(first) = line_iter.next() {
-        let last = line_iter.last().unwrap_or(first);
+        let last = line_iter.next_back().unwrap_or(first);
         let value = first * 10 + last;
         debug!(value);
         value
@@ -1,4 +1,7 @@
 use day_02::*;
+use divan::{black_box, Bencher};
+
+const INPUT: &str = include_str!("../input.txt");
 
 fn main() {
     // Run registered benchmarks.
@@ -6,15 +9,87 @@ fn main() {
 }
 
 #[divan::bench]
-fn part1() {
-    part1::process(
-        divan::black_box(include_str!("../input.txt",)),
-        divan::black_box(CubeCollection::new(12, 13, 14)),
-    )
-    .unwrap();
+fn part1_parse() {
+    part1::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part1_solve(bencher: Bencher) {
+    let games = part1::parse(INPUT).unwrap();
+    bencher.bench(|| {
+        part1::solve(
+            black_box(&games),
+            black_box(CubeCollection::new(12, 13, 14)),
+        )
+        .unwrap()
+    });
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+fn part2_parse() {
+    part2::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part2_solve(bencher: Bencher) {
+    let games = part2::parse(INPUT).unwrap();
+    bencher.bench(|| part2::solve(black_box(&games)).unwrap());
+}
+
+/// The same stages on generated records of a growing number of games.
+mod scaling {
+    use super::*;
+
+    const GAMES: &[usize] = &[100, 1_000, 10_000];
+
+    /// A record of `games` games of three rounds each.
+    fn generate(games: usize) -> String {
+        (1..=games)
+            .map(|id| {
+                format!(
+                    "Game {}: {} blue, {} red; {} red, {} green, {} blue; {} green",
+                    id,
+                    id % 17 + 1,
+                    id * 3 % 19 + 1,
+                    id * 7 % 13 + 1,
+                    id % 11 + 1,
+                    id * 5 % 16 + 1,
+                    id * 2 % 15 + 1
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[divan::bench(args = GAMES)]
+    fn part1_parse(bencher: Bencher, games: usize) {
+        let input = generate(games);
+        bencher.bench(|| part1::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = GAMES)]
+    fn part1_solve(bencher: Bencher, games: usize) {
+        let input = generate(games);
+        let games = part1::parse(&input).unwrap();
+        bencher.bench(|| {
+            part1::solve(
+                black_box(&games),
+                black_box(CubeCollection::new(12, 13, 14)),
+            )
+            .unwrap()
+        });
+    }
+
+    #[divan::bench(args = GAMES)]
+    fn part2_parse(bencher: Bencher, games: usize) {
+        let input = generate(games);
+        bencher.bench(|| part2::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = GAMES)]
+    fn part2_solve(bencher: Bencher, games: usize) {
+        let input = generate(games);
+        let games = part2::parse(&input).unwrap();
+        bencher.bench(|| part2::solve(black_box(&games)).unwrap());
+    }
 }
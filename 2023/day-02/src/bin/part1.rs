@@ -2,16 +2,15 @@ use anyhow::{Context, Result};
 use day_02::{part1::process, CubeCollection};
 
 #[cfg(feature = "dhat-heap")]
-#[global_allocator]
-static ALLOC: dhat::Alloc = dhat::Alloc;
+aoc_profiling::global_allocator!();
 
 #[tracing::instrument(level = "trace")]
 fn main() -> Result<()> {
     #[cfg(feature = "dhat-heap")]
-    let _profiler = dhat::Profiler::new_heap();
+    let _profiler = aoc_profiling::profile("day-02-part1");
 
     #[cfg(not(feature = "dhat-heap"))]
-    tracing_subscriber::fmt::init();
+    let _trace = aoc_tracing::init()?;
 
     let file = include_str!("../../input.txt");
     let result = process(file, CubeCollection::new(12, 13, 14)).context("process part 1")?;
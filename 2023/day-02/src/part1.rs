@@ -1,10 +1,20 @@
-use crate::{custom_error::AocError, parser::parse_games, CubeCollection};
+use crate::{custom_error::AocError, parser::parse_games, CubeCollection, Game};
 
 #[tracing::instrument(level = "trace", skip(input, total_cubes))]
-pub fn process<'a>(input: &'a str, total_cubes: CubeCollection) -> Result<u32, AocError> {
+pub fn process(input: &str, total_cubes: CubeCollection) -> Result<u32, AocError> {
+    solve(&parse(input)?, total_cubes)
+}
+
+#[tracing::instrument(level = "trace", skip(input))]
+pub fn parse(input: &str) -> Result<Vec<Game>, AocError> {
     let (_, games) = parse_games(input).map_err(|e| AocError::ParserError(e.to_string()))?;
+    Ok(games)
+}
+
+#[tracing::instrument(level = "trace", skip(games, total_cubes))]
+pub fn solve(games: &[Game], total_cubes: CubeCollection) -> Result<u32, AocError> {
     let valid_games_ids = games
-        .into_iter()
+        .iter()
         .filter_map(|game| game.is_valid(&total_cubes).then_some(game.id));
     let sum = valid_games_ids.sum();
     Ok(sum)
@@ -1,9 +1,19 @@
-use crate::{custom_error::AocError, parser::parse_games};
+use crate::{custom_error::AocError, parser::parse_games, Game};
 
 #[tracing::instrument(level = "trace", skip(input))]
 pub fn process(input: &str) -> Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(level = "trace", skip(input))]
+pub fn parse(input: &str) -> Result<Vec<Game>, AocError> {
     let (_, games) = parse_games(input).map_err(|e| AocError::ParserError(e.to_string()))?;
-    let cubes_in_games = games.into_iter().map(|game| game.cubes_in_game());
+    Ok(games)
+}
+
+#[tracing::instrument(level = "trace", skip(games))]
+pub fn solve(games: &[Game]) -> Result<u32, AocError> {
+    let cubes_in_games = games.iter().map(|game| game.cubes_in_game());
     let power = cubes_in_games.map(|cube| cube.red * cube.green * cube.blue);
     let power_sum = power.sum::<u32>();
     Ok(power_sum)
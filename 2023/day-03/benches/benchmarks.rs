@@ -1,4 +1,7 @@
 use day_03::*;
+use divan::{black_box, Bencher};
+
+const INPUT: &str = include_str!("../input.txt");
 
 fn main() {
     // Run registered benchmarks.
@@ -6,11 +9,71 @@ fn main() {
 }
 
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+fn part1_parse() {
+    part1::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part1_solve(bencher: Bencher) {
+    let engine_map = part1::parse(INPUT).unwrap();
+    bencher.bench(|| part1::solve(black_box(&engine_map)).unwrap());
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+fn part2_parse() {
+    part2::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part2_solve(bencher: Bencher) {
+    let engine_map = part2::parse(INPUT).unwrap();
+    bencher.bench(|| part2::solve(black_box(&engine_map)).unwrap());
+}
+
+/// The same stages on generated schematics of a growing number of rows.
+mod scaling {
+    use super::*;
+
+    const TILES: &[usize] = &[10, 100, 1_000];
+
+    /// The example schematic of the puzzle, stacked `tiles` times.
+    fn generate(tiles: usize) -> String {
+        indoc::indoc! {"
+            467..114..
+            ...*......
+            ..35..633.
+            ......#...
+            617*......
+            .....+.58.
+            ..592.....
+            ......755.
+            ...$.*....
+            .664.598..
+        "}
+        .repeat(tiles)
+    }
+
+    #[divan::bench(args = TILES)]
+    fn part1_parse(bencher: Bencher, tiles: usize) {
+        let input = generate(tiles);
+        bencher.bench(|| part1::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = TILES)]
+    fn part1_solve(bencher: Bencher, tiles: usize) {
+        let engine_map = part1::parse(&generate(tiles)).unwrap();
+        bencher.bench(|| part1::solve(black_box(&engine_map)).unwrap());
+    }
+
+    #[divan::bench(args = TILES)]
+    fn part2_parse(bencher: Bencher, tiles: usize) {
+        let input = generate(tiles);
+        bencher.bench(|| part2::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = TILES)]
+    fn part2_solve(bencher: Bencher, tiles: usize) {
+        let engine_map = part2::parse(&generate(tiles)).unwrap();
+        bencher.bench(|| part2::solve(black_box(&engine_map)).unwrap());
+    }
 }
@@ -43,7 +43,10 @@ pub struct Coord {
     pub y: i32,
 }
 
-pub fn parse_engine_map(input: &str) -> (Map<Coord, MapItem>, Map<NumberId, u32>) {
+/// The items of the engine schematic by their position, and the value of each number.
+pub type EngineMap = (Map<Coord, MapItem>, Map<NumberId, u32>);
+
+pub fn parse_engine_map(input: &str) -> EngineMap {
     let mut number_map: Map<NumberId, u32> = Default::default();
     let mut item_map: Map<Coord, MapItem> = Default::default();
     let mut number_id = Default::default();
@@ -2,15 +2,24 @@ use itertools::Itertools;
 
 use crate::{
     custom_error::AocError,
-    parser::{parse_engine_map, Coord, MapItem},
+    parser::{parse_engine_map, Coord, EngineMap, MapItem},
 };
 
 pub fn process(input: &str) -> Result<u32, AocError> {
-    let (item_map, mut number_map) = parse_engine_map(input);
+    solve(&parse(input)?)
+}
+
+pub fn parse(input: &str) -> Result<EngineMap, AocError> {
+    Ok(parse_engine_map(input))
+}
+
+pub fn solve((item_map, number_map): &EngineMap) -> Result<u32, AocError> {
+    // numbers are taken out once counted, so they aren't counted for several symbols
+    let mut number_map = number_map.clone();
 
     let mut try_take = |coord| -> Option<u32> {
         match item_map.get(&coord) {
-            Some(MapItem::Number(id)) => number_map.remove(&id),
+            Some(MapItem::Number(id)) => number_map.remove(id),
             _ => None,
         }
     };
@@ -30,7 +39,7 @@ pub fn process(input: &str) -> Result<u32, AocError> {
             try_take(coord + Coord { x: 1, y: 1 }),
         ]
         .into_iter()
-        .filter_map(|n| n)
+        .flatten()
         .collect_vec();
         if numbers.len() == 2 {
             Some((numbers[0], numbers[1]))
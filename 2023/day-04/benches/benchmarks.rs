@@ -1,4 +1,7 @@
 use day_04::*;
+use divan::{black_box, Bencher};
+
+const INPUT: &str = include_str!("../input.txt");
 
 fn main() {
     // Run registered benchmarks.
@@ -6,11 +9,84 @@ fn main() {
 }
 
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+fn part1_parse() {
+    part1::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part1_solve(bencher: Bencher) {
+    let cards = part1::parse(INPUT).unwrap();
+    bencher.bench(|| part1::solve(black_box(&cards)).unwrap());
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+fn part2_parse() {
+    part2::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part2_solve(bencher: Bencher) {
+    let cards = part2::parse(INPUT).unwrap();
+    bencher.bench(|| part2::solve(black_box(&cards)).unwrap());
+}
+
+/// The same stages on generated piles of a growing number of cards.
+mod scaling {
+    use super::*;
+
+    const CARDS: &[usize] = &[100, 1_000, 10_000];
+
+    /// A pile of `cards` cards with 5 winning numbers and 8 of our own each. The cards win 0, 1
+    /// or 2 numbers in turn, so the number of copies of part 2 only grows linearly.
+    fn generate(cards: usize) -> String {
+        (1..=cards)
+            .map(|card| {
+                let winning = (0..5).map(|i| 10 + (card + 7 * i) % 40).collect::<Vec<_>>();
+                let ours = winning
+                    .iter()
+                    .take(card % 3)
+                    .copied()
+                    .chain(60..)
+                    .take(8)
+                    .collect::<Vec<_>>();
+                let numbers = |numbers: &[usize]| {
+                    numbers
+                        .iter()
+                        .map(|n| format!("{:2}", n))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                format!(
+                    "Card {:4}: {} | {}\n",
+                    card,
+                    numbers(&winning),
+                    numbers(&ours)
+                )
+            })
+            .collect()
+    }
+
+    #[divan::bench(args = CARDS)]
+    fn part1_parse(bencher: Bencher, cards: usize) {
+        let input = generate(cards);
+        bencher.bench(|| part1::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = CARDS)]
+    fn part1_solve(bencher: Bencher, cards: usize) {
+        let cards = part1::parse(&generate(cards)).unwrap();
+        bencher.bench(|| part1::solve(black_box(&cards)).unwrap());
+    }
+
+    #[divan::bench(args = CARDS)]
+    fn part2_parse(bencher: Bencher, cards: usize) {
+        let input = generate(cards);
+        bencher.bench(|| part2::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = CARDS)]
+    fn part2_solve(bencher: Bencher, cards: usize) {
+        let cards = part2::parse(&generate(cards)).unwrap();
+        bencher.bench(|| part2::solve(black_box(&cards)).unwrap());
+    }
 }
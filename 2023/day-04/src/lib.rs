@@ -1,5 +1,5 @@
 pub mod custom_error;
 
-mod parser;
+pub mod parser;
 pub mod part1;
 pub mod part2;
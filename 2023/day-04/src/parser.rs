@@ -9,7 +9,13 @@ use nom::{
     IResult,
 };
 
-pub fn parse(input: &str) -> IResult<&str, BTreeMap<u32, (HashSet<u32>, HashSet<u32>)>> {
+/// The winning numbers and our numbers of a card.
+pub type Numbers = (HashSet<u32>, HashSet<u32>);
+
+/// The numbers of every card, by the number of the card.
+pub type Cards = BTreeMap<u32, Numbers>;
+
+pub fn parse(input: &str) -> IResult<&str, Cards> {
     let (input, result) = complete(fold_many1(
         terminated(line_parser, line_ending),
         BTreeMap::new,
@@ -29,7 +35,7 @@ fn set(input: &str) -> IResult<&str, HashSet<u32>> {
     })(input)
 }
 
-fn line_parser(input: &str) -> IResult<&str, (u32, (HashSet<u32>, HashSet<u32>))> {
+fn line_parser(input: &str) -> IResult<&str, (u32, Numbers)> {
     let (input, card) = delimited(tuple((tag("Card"), space1)), u32, tag(":"))(input)?;
     let (input, (winning, ours)) = separated_pair(set, tag(" |"), set)(input)?;
     Ok((input, (card, (winning, ours))))
@@ -1,11 +1,21 @@
-use crate::{custom_error::AocError, parser::parse};
+use crate::{
+    custom_error::AocError,
+    parser::{self, Cards},
+};
 
 pub fn process(input: &str) -> Result<u32, AocError> {
-    let (_, cards) = parse(input).map_err(|e| AocError::ParserError(e.to_string()))?;
+    solve(&parse(input)?)
+}
+
+pub fn parse(input: &str) -> Result<Cards, AocError> {
+    let (_, cards) = parser::parse(input).map_err(|e| AocError::ParserError(e.to_string()))?;
+    Ok(cards)
+}
 
+pub fn solve(cards: &Cards) -> Result<u32, AocError> {
     let result = cards
-        .into_iter()
-        .map(|(_, (winning, ours))| winning.intersection(&ours).count() as u32)
+        .iter()
+        .map(|(_, (winning, ours))| winning.intersection(ours).count() as u32)
         .filter_map(|wins| wins.checked_sub(1).map(|wins| 2u32.pow(wins)))
         .sum();
     Ok(result)
@@ -19,7 +29,7 @@ mod tests {
 
     #[rstest]
     fn test_process() -> Result<()> {
-        tracing_subscriber::fmt::init();
+        let _ = tracing_subscriber::fmt::try_init();
         let input = indoc::indoc! {r#"
             Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
             Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
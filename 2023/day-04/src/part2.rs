@@ -1,13 +1,23 @@
 use std::collections::HashMap;
 
-use crate::{custom_error::AocError, parser::parse};
+use crate::{
+    custom_error::AocError,
+    parser::{self, Cards},
+};
 
 pub fn process(input: &str) -> Result<u32, AocError> {
-    let (_, cards) = parse(input).map_err(|e| AocError::ParserError(e.to_string()))?;
+    solve(&parse(input)?)
+}
+
+pub fn parse(input: &str) -> Result<Cards, AocError> {
+    let (_, cards) = parser::parse(input).map_err(|e| AocError::ParserError(e.to_string()))?;
+    Ok(cards)
+}
 
+pub fn solve(cards: &Cards) -> Result<u32, AocError> {
     let result = cards
-        .into_iter()
-        .map(|(card, (winning, ours))| (card, winning.intersection(&ours).count() as u32))
+        .iter()
+        .map(|(&card, (winning, ours))| (card, winning.intersection(ours).count() as u32))
         .fold(HashMap::new(), |mut map, (card, wins)| {
             let number_of_cards = *map.entry(card).and_modify(|e| *e += 1u32).or_insert(1);
             for card_won in (card + 1)..=(card + wins) {
@@ -31,7 +41,7 @@ mod tests {
 
     #[rstest]
     fn test_process() -> Result<()> {
-        tracing_subscriber::fmt::init();
+        let _ = tracing_subscriber::fmt::try_init();
         let input = indoc::indoc! {r#"
             Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
             Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
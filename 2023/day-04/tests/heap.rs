@@ -0,0 +1,31 @@
+//! Allocations of the parts, in a test binary of their own as it installs the dhat allocator.
+
+use day_04::*;
+
+aoc_profiling::global_allocator!();
+
+const INPUT: &str = include_str!("../input.txt");
+
+#[test]
+fn part1_allocations() {
+    let heap = aoc_profiling::heap_meter();
+    let (_, usage) = heap.measure(|| part1::process(INPUT).unwrap());
+    usage.assert_blocks_below(2_000);
+    usage.assert_max_bytes_below(128 * 1024);
+}
+
+#[test]
+fn part2_allocations() {
+    let heap = aoc_profiling::heap_meter();
+    let (_, usage) = heap.measure(|| part2::process(INPUT).unwrap());
+    usage.assert_blocks_below(2_000);
+    usage.assert_max_bytes_below(128 * 1024);
+}
+
+#[test]
+fn part2_solve_barely_allocates() {
+    let heap = aoc_profiling::heap_meter();
+    let cards = part2::parse(INPUT).unwrap();
+    let (_, usage) = heap.measure(|| part2::solve(&cards).unwrap());
+    usage.assert_blocks_below(16);
+}
@@ -1,4 +1,7 @@
 use day_05::*;
+use divan::{black_box, Bencher};
+
+const INPUT: &str = include_str!("../input.txt");
 
 fn main() {
     // Run registered benchmarks.
@@ -6,17 +9,86 @@ fn main() {
 }
 
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input.txt",
-    )))
-    .unwrap();
+fn part1_parse() {
+    part1::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part1_solve(bencher: Bencher) {
+    let seed_map = part1::parse(INPUT).unwrap();
+    bencher.bench(|| part1::solve(black_box(&seed_map)).unwrap());
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input.txt",
-    )))
-    .unwrap();
-}
\ No newline at end of file
+fn part2_parse() {
+    part2::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part2_solve(bencher: Bencher) {
+    let seed_map = part2::parse(INPUT).unwrap();
+    bencher.bench(|| part2::solve(black_box(&seed_map)).unwrap());
+}
+
+/// The same stages on generated almanacs of a growing number of seeds and map entries.
+mod scaling {
+    use super::*;
+
+    const ENTRIES: &[usize] = &[10, 100, 1_000];
+
+    /// An almanac with `entries` pairs of seed numbers, and `entries` entries in each map. The
+    /// ranges of seeds of part 2 are kept short, as it tries every seed in them.
+    fn generate(entries: usize) -> String {
+        const MAPS: [&str; 7] = [
+            "seed-to-soil",
+            "soil-to-fertilizer",
+            "fertilizer-to-water",
+            "water-to-light",
+            "light-to-temperature",
+            "temperature-to-humidity",
+            "humidity-to-location",
+        ];
+        let seeds = (0..entries)
+            .map(|i| format!("{} 10", i * 1000 + 3))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let maps = MAPS.iter().enumerate().map(|(map, name)| {
+            let entries = (0..entries)
+                .map(|i| {
+                    let destination = (i * 7919 + map) % entries;
+                    format!("{} {} 1000\n", destination * 1000, i * 1000)
+                })
+                .collect::<String>();
+            format!("{} map:\n{}", name, entries)
+        });
+        format!(
+            "seeds: {}\n\n{}",
+            seeds,
+            maps.collect::<Vec<_>>().join("\n")
+        )
+    }
+
+    #[divan::bench(args = ENTRIES)]
+    fn part1_parse(bencher: Bencher, entries: usize) {
+        let input = generate(entries);
+        bencher.bench(|| part1::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = ENTRIES)]
+    fn part1_solve(bencher: Bencher, entries: usize) {
+        let seed_map = part1::parse(&generate(entries)).unwrap();
+        bencher.bench(|| part1::solve(black_box(&seed_map)).unwrap());
+    }
+
+    #[divan::bench(args = ENTRIES)]
+    fn part2_parse(bencher: Bencher, entries: usize) {
+        let input = generate(entries);
+        bencher.bench(|| part2::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = ENTRIES)]
+    fn part2_solve(bencher: Bencher, entries: usize) {
+        let seed_map = part2::parse(&generate(entries)).unwrap();
+        bencher.bench(|| part2::solve(black_box(&seed_map)).unwrap());
+    }
+}
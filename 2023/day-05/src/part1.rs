@@ -2,14 +2,24 @@ mod parser;
 mod range_map;
 mod seed_map;
 
-use parser::parse;
+pub use seed_map::SeedMap;
 use tracing::*;
 
 use crate::custom_error::AocError;
 
-#[tracing::instrument(level = "trace", skip(_input))]
-pub fn process(_input: &str) -> Result<u32, AocError> {
-    let (_, seed_map) = parse(_input).map_err(|e| AocError::ParserError(e.to_string()))?;
+#[tracing::instrument(level = "trace", skip(input))]
+pub fn process(input: &str) -> Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(level = "trace", skip(input))]
+pub fn parse(input: &str) -> Result<SeedMap, AocError> {
+    let (_, seed_map) = parser::parse(input).map_err(|e| AocError::ParserError(e.to_string()))?;
+    Ok(seed_map)
+}
+
+#[tracing::instrument(level = "trace", skip(seed_map))]
+pub fn solve(seed_map: &SeedMap) -> Result<u32, AocError> {
     let min = seed_map
         .seeds
         .iter()
@@ -74,7 +84,7 @@ mod tests {
     #[tracing::instrument(level = "trace", skip())]
     #[test(rstest)]
     fn test_range_map(input: &str) -> Result<()> {
-        let (_, seed_map) = parse(input).unwrap();
+        let seed_map = parse(input)?;
         assert_eq!(
             vec![81, 14, 57, 13],
             seed_map
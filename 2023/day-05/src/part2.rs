@@ -2,20 +2,29 @@ mod parser;
 mod range_map;
 mod seed_map;
 
-use parser::parse;
 use rayon::iter::ParallelIterator;
+pub use seed_map::SeedMap;
 use tracing::*;
 
 use crate::custom_error::AocError;
 
+#[tracing::instrument(level = "trace", skip(input))]
+pub fn process(input: &str) -> Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(level = "trace", skip(input))]
+pub fn parse(input: &str) -> Result<SeedMap, AocError> {
+    let (_, seed_map) = parser::parse(input).map_err(|e| AocError::ParserError(e.to_string()))?;
+    Ok(seed_map)
+}
+
 // NAIVE APPROACH!!!
 // Can be solved a lot more efficient by considering overlapping ranges and create a projection
 // directly from seed into location.
 
-#[tracing::instrument(level = "trace", skip(_input))]
-pub fn process(_input: &str) -> Result<u32, AocError> {
-    let (_, seed_map) = parse(_input).map_err(|e| AocError::ParserError(e.to_string()))?;
-
+#[tracing::instrument(level = "trace", skip(seed_map))]
+pub fn solve(seed_map: &SeedMap) -> Result<u32, AocError> {
     let min = seed_map
         .seeds
         .destination_ranges()
@@ -38,6 +38,6 @@ impl RangeMap {
 
 impl RangeMapEntry {
     pub fn destination_range(&self) -> Range<u32> {
-        (self.destination_start..self.destination_start + self.range_length).into_iter()
+        self.destination_start..self.destination_start + self.range_length
     }
 }
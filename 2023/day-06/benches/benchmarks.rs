@@ -1,4 +1,7 @@
 use day_06::*;
+use divan::{black_box, Bencher};
+
+const INPUT: &str = include_str!("../input.txt");
 
 fn main() {
     // Run registered benchmarks.
@@ -6,17 +9,60 @@ fn main() {
 }
 
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input.txt",
-    )))
-    .unwrap();
+fn part1_parse() {
+    part1::parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn part1_solve(bencher: Bencher) {
+    let races = part1::parse(INPUT).unwrap();
+    bencher.bench(|| part1::solve(black_box(&races)).unwrap());
+}
+
+#[divan::bench]
+fn part2_parse() {
+    part2::parse(black_box(INPUT)).unwrap();
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input.txt",
-    )))
-    .unwrap();
-}
\ No newline at end of file
+fn part2_solve(bencher: Bencher) {
+    let race = part2::parse(INPUT).unwrap();
+    bencher.bench(|| part2::solve(black_box(&race)).unwrap());
+}
+
+/// The stages of part 1 on generated sheets of a growing number of races. Part 2 reads all races
+/// as a single one, whose time has to fit into a `u64`, so its input can't grow.
+mod scaling {
+    use super::*;
+
+    const RACES: &[usize] = &[10, 100, 1_000];
+
+    /// A sheet of `races` races, that can each only be won in a single way, so their product
+    /// doesn't overflow.
+    fn generate(races: usize) -> String {
+        let hold = (0..races).map(|i| 10 + i % 1000).collect::<Vec<_>>();
+        let line = |numbers: Vec<usize>| {
+            numbers
+                .iter()
+                .map(|n| format!("{:8}", n))
+                .collect::<String>()
+        };
+        format!(
+            "Time:{}\nDistance:{}\n",
+            line(hold.iter().map(|h| 2 * h).collect()),
+            line(hold.iter().map(|h| h * h - 1).collect())
+        )
+    }
+
+    #[divan::bench(args = RACES)]
+    fn part1_parse(bencher: Bencher, races: usize) {
+        let input = generate(races);
+        bencher.bench(|| part1::parse(black_box(&input)).unwrap());
+    }
+
+    #[divan::bench(args = RACES)]
+    fn part1_solve(bencher: Bencher, races: usize) {
+        let races = part1::parse(&generate(races)).unwrap();
+        bencher.bench(|| part1::solve(black_box(&races)).unwrap());
+    }
+}
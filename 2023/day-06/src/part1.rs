@@ -1,4 +1,3 @@
-use itertools::Itertools;
 use tracing::*;
 
 use crate::custom_error::AocError;
@@ -6,11 +5,17 @@ use crate::custom_error::AocError;
 mod parser;
 
 pub fn process(input: &str) -> Result<u32, AocError> {
-    let (_, input) = parser::parse(input).map_err(|e| AocError::ParserError(e.to_string()))?;
+    solve(&parse(input)?)
+}
 
-    let input = input.collect_vec();
+/// The time and record distance of every race.
+pub fn parse(input: &str) -> Result<Vec<(u32, u32)>, AocError> {
+    let (_, races) = parser::parse(input).map_err(|e| AocError::ParserError(e.to_string()))?;
+    Ok(races)
+}
 
-    let r = input.into_iter().map(|(time, distance)| {
+pub fn solve(races: &[(u32, u32)]) -> Result<u32, AocError> {
+    let r = races.iter().map(|&(time, distance)| {
         let time = time as f64;
         let distance = distance as f64;
         let h = ((time) - (time * time - 4. * distance).sqrt()) / 2.0;
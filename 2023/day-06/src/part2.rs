@@ -4,9 +4,16 @@ use tracing::*;
 mod parser;
 
 pub fn process(input: &str) -> Result<u64, AocError> {
-    let (_, (time, distance)) =
-        parser::parse(input).map_err(|e| AocError::ParserError(e.to_string()))?;
+    solve(&parse(input)?)
+}
+
+/// The time and record distance of the single race.
+pub fn parse(input: &str) -> Result<(u64, u64), AocError> {
+    let (_, race) = parser::parse(input).map_err(|e| AocError::ParserError(e.to_string()))?;
+    Ok(race)
+}
 
+pub fn solve(&(time, distance): &(u64, u64)) -> Result<u64, AocError> {
     let time = time as f64;
     let distance = distance as f64;
     let h = ((time) - (time * time - 4. * distance).sqrt()) / 2.0;
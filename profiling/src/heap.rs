@@ -0,0 +1,137 @@
+//! Heap profiling with dhat.
+
+use std::{
+    hint::black_box,
+    sync::{Mutex, MutexGuard},
+};
+
+pub use dhat::{Alloc, Profiler};
+
+/// Makes dhat's [`Alloc`] the global allocator. Without a running profiler it only passes the
+/// allocations on to the system allocator.
+#[macro_export]
+macro_rules! global_allocator {
+    () => {
+        #[global_allocator]
+        static DHAT_ALLOC: $crate::Alloc = $crate::Alloc;
+    };
+}
+
+/// Profiles the heap until dropped, then writes the profile to `dhat-heap-<name>.json` and
+/// prints a summary.
+pub fn profile(name: &str) -> Profiler {
+    Profiler::builder()
+        .file_name(format!("dhat-heap-{}.json", name))
+        .build()
+}
+
+/// Only one profiler can run at a time, tests measuring the heap take turns.
+static MEASURING: Mutex<()> = Mutex::new(());
+
+/// What a closure allocated on the heap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeapUsage {
+    /// Allocations, counting every reallocation as a new one.
+    pub total_blocks: u64,
+    pub total_bytes: u64,
+    /// Blocks allocated when the heap was at its largest.
+    pub max_blocks: usize,
+    /// Most bytes allocated at once.
+    pub max_bytes: usize,
+}
+
+impl HeapUsage {
+    #[track_caller]
+    pub fn assert_blocks_below(&self, blocks: u64) {
+        assert!(
+            self.total_blocks < blocks,
+            "allocated {} blocks, expected fewer than {}",
+            self.total_blocks,
+            blocks
+        );
+    }
+
+    #[track_caller]
+    pub fn assert_max_bytes_below(&self, bytes: usize) {
+        assert!(
+            self.max_bytes < bytes,
+            "allocated {} bytes at most, expected fewer than {}",
+            self.max_bytes,
+            bytes
+        );
+    }
+}
+
+/// Exclusive use of the heap profiler, see [`heap_meter`].
+pub struct HeapMeter {
+    _measuring: MutexGuard<'static, ()>,
+}
+
+/// Takes the heap profiler, waiting for other tests holding it to finish. Tests hold it for all
+/// of their work, setting up included, as allocations of any thread are measured.
+pub fn heap_meter() -> HeapMeter {
+    HeapMeter {
+        _measuring: MEASURING.lock().unwrap_or_else(|e| e.into_inner()),
+    }
+}
+
+impl HeapMeter {
+    /// Runs `f` under a profiler, measuring what it allocates.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless [`Alloc`] is the global allocator, as nothing would be measured otherwise.
+    pub fn measure<T>(&self, f: impl FnOnce() -> T) -> (T, HeapUsage) {
+        let _profiler = Profiler::builder().testing().build();
+
+        drop(black_box(Box::new(0u8)));
+        let before = dhat::HeapStats::get();
+        assert!(
+            before.total_blocks > 0,
+            "measuring the heap needs aoc_profiling::global_allocator!() in the binary"
+        );
+        let result = f();
+        let after = dhat::HeapStats::get();
+        let usage = HeapUsage {
+            total_blocks: after.total_blocks - before.total_blocks,
+            total_bytes: after.total_bytes - before.total_bytes,
+            max_blocks: after.max_blocks,
+            max_bytes: after.max_bytes,
+        };
+        (result, usage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    global_allocator!();
+
+    #[test]
+    fn measure_heap() {
+        let heap = heap_meter();
+        let (len, usage) = heap.measure(|| {
+            let mut v = Vec::with_capacity(1024);
+            v.extend(0..2048u32);
+            v.len()
+        });
+        assert_eq!(2048, len);
+        // the allocation and its growth
+        assert_eq!(2, usage.total_blocks);
+        assert_eq!((4096 + 8192) as u64, usage.total_bytes);
+        assert_eq!(8192, usage.max_bytes);
+        usage.assert_blocks_below(3);
+        usage.assert_max_bytes_below(8193);
+
+        let ((), usage) = heap.measure(|| ());
+        assert_eq!(0, usage.total_blocks);
+
+        let (_, usage) = heap.measure(|| black_box(vec![1u8]).repeat(2));
+        let message = *std::panic::catch_unwind(|| usage.assert_blocks_below(2))
+            .unwrap_err()
+            .downcast::<String>()
+            .unwrap();
+        assert_eq!("allocated 2 blocks, expected fewer than 2", message);
+    }
+}
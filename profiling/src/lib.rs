@@ -0,0 +1,33 @@
+//! Heap profiling with [dhat](https://docs.rs/dhat/0.3.2/dhat/), and a lighter count of time
+//! and allocations, shared by the runners and tests of every year.
+//!
+//! With the `dhat` feature, on by default, binaries install dhat's allocator with
+//! [`global_allocator!`], usually behind a `dhat-heap` feature of their own, and wrap every part
+//! in a [`profile`]. The profiles are viewed with
+//! <https://nnethercote.github.io/dh_view/dh_view.html>.
+//!
+//! Tests install the allocator in a test binary of their own, `tests/heap.rs`, and check what a
+//! part allocates with a [`HeapMeter`]. Every test of the binary holds the meter from start to
+//! end, so the tests take turns and none allocates while another measures:
+//!
+//! ```ignore
+//! aoc_profiling::global_allocator!();
+//!
+//! #[test]
+//! fn part2_allocations() {
+//!     let heap = aoc_profiling::heap_meter();
+//!     let cards = part2::parse(INPUT).unwrap();
+//!     let (_, usage) = heap.measure(|| part2::solve(&cards));
+//!     usage.assert_blocks_below(2_000);
+//! }
+//! ```
+//!
+//! Runners reporting the time and heap usage of every part without a build of their own use
+//! [`stats`] instead.
+
+#[cfg(feature = "dhat")]
+mod heap;
+pub mod stats;
+
+#[cfg(feature = "dhat")]
+pub use heap::*;
@@ -0,0 +1,226 @@
+//! Wall time and heap usage of solving parts, for runners reporting them on request.
+//!
+//! The heap is tracked by [`CountingAlloc`], which has to be the global allocator of the binary
+//! for allocations to be counted. It only keeps a few counters on top of the system allocator, so
+//! unlike dhat it doesn't need a build of its own, and until [`CountingAlloc::enable`] is called
+//! it doesn't even do that: runs without a report allocate as they would with the system
+//! allocator, but for the check of a flag.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicIsize, AtomicU64, Ordering::Relaxed},
+    time::{Duration, Instant},
+};
+
+/// Makes [`CountingAlloc`] the global allocator.
+#[macro_export]
+macro_rules! counting_allocator {
+    () => {
+        #[global_allocator]
+        static COUNTING_ALLOC: $crate::stats::CountingAlloc = $crate::stats::CountingAlloc;
+    };
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// Bytes in use, counted from when counting was enabled. Blocks allocated before that and freed
+/// after take it below zero.
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator, counting allocations and the bytes in use once enabled.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    /// Starts counting the allocations of every thread.
+    pub fn enable() {
+        ENABLED.store(true, Relaxed);
+    }
+
+    pub fn is_enabled() -> bool {
+        ENABLED.load(Relaxed)
+    }
+
+    fn allocated(size: usize) {
+        if Self::is_enabled() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            Self::grow(size as isize);
+        }
+    }
+
+    fn grow(size: isize) {
+        let current = CURRENT.fetch_add(size, Relaxed) + size;
+        PEAK.fetch_max(current, Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if Self::is_enabled() {
+            CURRENT.fetch_sub(layout.size() as isize, Relaxed);
+        }
+    }
+
+    /// Reallocations are counted as allocations, as they may well move the block.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && Self::is_enabled() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            Self::grow(new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+/// What solving a part took.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Usage {
+    pub wall: Duration,
+    /// Most bytes on the heap at once, on top of those in use when solving started.
+    pub peak_bytes: usize,
+    pub allocations: u64,
+}
+
+/// Runs `f`, measuring its [`Usage`]. Allocations of other threads meanwhile are counted as well,
+/// and none at all unless [`CountingAlloc`] is the global allocator and enabled.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let baseline = CURRENT.load(Relaxed);
+    PEAK.store(baseline, Relaxed);
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let start = Instant::now();
+    let result = f();
+    let usage = Usage {
+        wall: start.elapsed(),
+        peak_bytes: (PEAK.load(Relaxed) - baseline).max(0) as usize,
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+    };
+    (result, usage)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartUsage {
+    pub year: u32,
+    pub day: u32,
+    pub part: usize,
+    pub usage: Usage,
+}
+
+pub fn format_ns(ns: u64) -> String {
+    match ns {
+        0..=9_999 => format!("{} ns", ns),
+        10_000..=9_999_999 => format!("{:.1} µs", ns as f64 / 1e3),
+        10_000_000..=9_999_999_999 => format!("{:.1} ms", ns as f64 / 1e6),
+        _ => format!("{:.2} s", ns as f64 / 1e9),
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        0..=9_999 => format!("{} B", bytes),
+        10_000..=9_999_999 => format!("{:.1} KiB", bytes as f64 / 1024.),
+        _ => format!("{:.1} MiB", bytes as f64 / (1024. * 1024.)),
+    }
+}
+
+/// A table of the usage of every part, ending in a total over all of them when there are several:
+/// the time and allocations summed up, and the largest peak.
+pub fn report(parts: &[PartUsage]) -> String {
+    let row = |year: &str, day: &str, part: &str, usage: &Usage| {
+        format!(
+            "{:>5}  {:>3}  {:>4}  {:>10}  {:>10}  {:>11}\n",
+            year,
+            day,
+            part,
+            format_ns(usage.wall.as_nanos() as u64),
+            format_bytes(usage.peak_bytes),
+            usage.allocations
+        )
+    };
+    let mut table = format!(
+        "{:>5}  {:>3}  {:>4}  {:>10}  {:>10}  {:>11}\n",
+        "year", "day", "part", "time", "peak heap", "allocations"
+    );
+    for p in parts {
+        table.push_str(&row(
+            &p.year.to_string(),
+            &p.day.to_string(),
+            &p.part.to_string(),
+            &p.usage,
+        ));
+    }
+    if parts.len() > 1 {
+        let total = parts.iter().fold(Usage::default(), |total, p| Usage {
+            wall: total.wall + p.usage.wall,
+            peak_bytes: total.peak_bytes.max(p.usage.peak_bytes),
+            allocations: total.allocations + p.usage.allocations,
+        });
+        table.push_str(&row("total", "", "", &total));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatting() {
+        assert_eq!("999 ns", format_ns(999));
+        assert_eq!("12.3 µs", format_ns(12_345));
+        assert_eq!("45.7 ms", format_ns(45_678_901));
+        assert_eq!("12.00 s", format_ns(12_000_000_000));
+        assert_eq!("512 B", format_bytes(512));
+        assert_eq!("20.0 KiB", format_bytes(20_480));
+        assert_eq!("12.0 MiB", format_bytes(12 << 20));
+    }
+
+    #[test]
+    fn usage_report() {
+        let usage = |ms, peak_bytes, allocations| Usage {
+            wall: Duration::from_millis(ms),
+            peak_bytes,
+            allocations,
+        };
+        let parts = [
+            PartUsage {
+                year: 2023,
+                day: 1,
+                part: 1,
+                usage: usage(20, 512, 3),
+            },
+            PartUsage {
+                year: 2023,
+                day: 1,
+                part: 2,
+                usage: usage(30, 20_480, 40),
+            },
+        ];
+        assert_eq!(
+            concat!(
+                " year  day  part        time   peak heap  allocations\n",
+                " 2023    1     1     20.0 ms       512 B            3\n",
+                " 2023    1     2     30.0 ms    20.0 KiB           40\n",
+                "total                50.0 ms    20.0 KiB           43\n",
+            ),
+            report(&parts)
+        );
+        assert_eq!(2, report(&parts[..1]).lines().count());
+    }
+}
@@ -0,0 +1,34 @@
+//! The counting allocator, in a test binary of its own as it has to be the global allocator.
+
+use aoc_profiling::stats::{measure, CountingAlloc};
+
+aoc_profiling::counting_allocator!();
+
+#[test]
+fn measure_allocations() {
+    let allocate = || {
+        let mut v = vec![0u8; 1 << 20];
+        v.extend_from_slice(&[1; 1 << 20]);
+        v.len()
+    };
+    let kept = vec![0u8; 1 << 10];
+
+    // nothing is counted until enabled
+    let (len, usage) = measure(allocate);
+    assert_eq!(2 << 20, len);
+    assert_eq!(0, usage.allocations);
+    assert_eq!(0, usage.peak_bytes);
+
+    CountingAlloc::enable();
+    let (len, usage) = measure(allocate);
+    assert_eq!(2 << 20, len);
+    assert!(usage.allocations >= 2);
+    assert!(usage.peak_bytes >= 2 << 20);
+
+    // freeing a block allocated before counting started
+    let ((), usage) = measure(|| drop(kept));
+    assert_eq!(0, usage.peak_bytes);
+    let (_, usage) = measure(|| vec![0u8; 100]);
+    assert_eq!(1, usage.allocations);
+    assert_eq!(100, usage.peak_bytes);
+}